@@ -5,10 +5,23 @@ use futures::{
     task::{Context, Poll},
 };
 use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Sender<T> {
     inner: mpsc::Sender<T>,
+    /// the number of messages sent but not received yet.
+    len: Arc<AtomicUsize>,
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            len: self.len.clone(),
+        }
+    }
 }
 
 impl<T> Sink<T> for Sender<T> {
@@ -19,7 +32,13 @@ impl<T> Sink<T> for Sender<T> {
     }
 
     fn start_send(mut self: Pin<&mut Self>, msg: T) -> Result<(), Self::Error> {
-        (*self).inner.start_send(msg)
+        // counted before sending, or the receiver could decrement the counter below zero first.
+        self.len.fetch_add(1, Ordering::Relaxed);
+        let result = (*self).inner.start_send(msg);
+        if result.is_err() {
+            self.len.fetch_sub(1, Ordering::Relaxed);
+        }
+        result
     }
 
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
@@ -33,16 +52,28 @@ impl<T> Sink<T> for Sender<T> {
 
 impl<T> Sender<T> {
     pub fn try_send(&mut self, msg: T) -> Result<(), mpsc::SendError> {
-        (*self)
-            .inner
-            .try_send(msg)
-            .map_err(mpsc::TrySendError::into_send_error)
+        self.len.fetch_add(1, Ordering::Relaxed);
+        let result = (*self).inner.try_send(msg);
+        if result.is_err() {
+            self.len.fetch_sub(1, Ordering::Relaxed);
+        }
+        result.map_err(mpsc::TrySendError::into_send_error)
+    }
+
+    /// the number of messages waiting in the queue.
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
 
 #[derive(Debug)]
 pub struct Receiver<T> {
     inner: mpsc::Receiver<T>,
+    len: Arc<AtomicUsize>,
 }
 
 impl<T> FusedStream for Receiver<T>
@@ -58,11 +89,48 @@ impl<T> Stream for Receiver<T> {
     type Item = T;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        Pin::new(&mut self.inner).poll_next(cx)
+        let result = Pin::new(&mut self.inner).poll_next(cx);
+        if let Poll::Ready(Some(_)) = result {
+            self.len.fetch_sub(1, Ordering::Relaxed);
+        }
+        result
     }
 }
 
 pub fn new<T>(size: usize) -> (Sender<T>, Receiver<T>) {
     let (sender, receiver) = mpsc::channel(size);
-    (Sender { inner: sender }, Receiver { inner: receiver })
+    let len = Arc::new(AtomicUsize::new(0));
+    (
+        Sender {
+            inner: sender,
+            len: len.clone(),
+        },
+        Receiver {
+            inner: receiver,
+            len,
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use futures::StreamExt;
+
+    #[test]
+    fn test_len() {
+        let (mut sender, mut receiver) = super::new(16);
+        sender.try_send(1).unwrap();
+        sender.clone().try_send(2).unwrap();
+        assert_eq!(sender.len(), 2);
+        futures::executor::block_on(receiver.next());
+        assert_eq!(sender.len(), 1);
+    }
+
+    #[test]
+    fn test_len_when_full() {
+        let (mut sender, _receiver) = super::new(0);
+        sender.try_send(1).unwrap();
+        assert!(sender.try_send(2).is_err());
+        assert_eq!(sender.len(), 1);
+    }
 }
@@ -1,6 +1,9 @@
 use channel::Receiver;
 use futures::StreamExt;
 use logger::prelude::*;
+use metrics::trace::KeyValue;
+use notifier::status::CheckerStatuses;
+use notifier::{Alert, AlertKind, AlertSender};
 use std::sync::mpsc::SyncSender;
 
 #[derive(Debug)]
@@ -12,42 +15,84 @@ pub enum IsSyncingMessage {
 pub struct IsSyncingChecker {
     teritorid_endpoint: String,
     receiver: Receiver<IsSyncingMessage>,
+    alert_sender: AlertSender,
+    statuses: CheckerStatuses,
 }
 
 impl IsSyncingChecker {
-    pub fn new(teritorid_endpoint: String, receiver: Receiver<IsSyncingMessage>) -> Self {
+    pub fn new(
+        teritorid_endpoint: String,
+        receiver: Receiver<IsSyncingMessage>,
+        alert_sender: AlertSender,
+        statuses: CheckerStatuses,
+    ) -> Self {
         Self {
             teritorid_endpoint,
             receiver,
+            alert_sender,
+            statuses,
         }
     }
+    async fn check(&mut self) {
+        match teritoricli::lock_client(&self.teritorid_endpoint)
+            .await
+            .fetch_syncing()
+            .await
+        {
+            Ok(syncing) => {
+                metrics::SYNCING
+                    .with_label_values(&[self.teritorid_endpoint.as_str()])
+                    .set(syncing as i64);
+                self.statuses
+                    .set_syncing(self.teritorid_endpoint.as_str(), syncing);
+                self.statuses.checked(
+                    self.teritorid_endpoint.as_str(),
+                    AlertKind::IsSyncing.as_str(),
+                );
+                if syncing {
+                    self.alert_sender.emit(Alert::new(
+                        AlertKind::IsSyncing,
+                        self.teritorid_endpoint.as_str(),
+                        format!(
+                            "the teritori daemon: {} is syncing",
+                            self.teritorid_endpoint.as_str()
+                        ),
+                    ));
+                } else {
+                    info!(
+                        "the teritori daemon: {} is synced",
+                        self.teritorid_endpoint.as_str()
+                    );
+                    self.alert_sender.emit(Alert::resolved(
+                        AlertKind::IsSyncing,
+                        self.teritorid_endpoint.as_str(),
+                        format!(
+                            "the teritori daemon: {} is synced",
+                            self.teritorid_endpoint.as_str()
+                        ),
+                    ));
+                }
+            }
+            Err(err) => {
+                let mut alert = Alert::new(
+                    AlertKind::CheckFailure,
+                    self.teritorid_endpoint.as_str(),
+                    err.to_string(),
+                );
+                alert.field("check", AlertKind::IsSyncing);
+                self.alert_sender.emit(alert);
+            }
+        }
+    }
+
     pub async fn run(mut self) {
         while let Some(message) = self.receiver.next().await {
             match message {
                 IsSyncingMessage::Check => {
-                    match teritoricli::get_client(&self.teritorid_endpoint)
-                        .lock()
-                        .await
-                        .fetch_syncing()
-                        .await
-                    {
-                        Ok(syncing) => {
-                            if syncing {
-                                error!(
-                                    "the teritori daemon: {} is syncing",
-                                    self.teritorid_endpoint.as_str()
-                                );
-                            } else {
-                                info!(
-                                    "the teritori daemon: {} is synced",
-                                    self.teritorid_endpoint.as_str()
-                                );
-                            }
-                        }
-                        Err(err) => {
-                            error!("{}", err);
-                        }
-                    }
+                    let attributes =
+                        vec![KeyValue::new("endpoint", self.teritorid_endpoint.clone())];
+                    metrics::trace::in_span("IsSyncingChecker::check", attributes, self.check())
+                        .await;
                 }
                 IsSyncingMessage::Terminate(sender) => {
                     info!("is syncing checker will be terminated soon...");
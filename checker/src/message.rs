@@ -23,3 +23,14 @@ impl From<proto::cosmos::base::tendermint::v1beta1::GetBlockByHeightResponse> fo
         }
     }
 }
+
+impl BlockMessage {
+    /// the height of the block. (0 if the header is missing)
+    pub fn height(&self) -> i64 {
+        self.block
+            .as_ref()
+            .and_then(|block| block.header.as_ref())
+            .map(|header| header.height)
+            .unwrap_or_default()
+    }
+}
@@ -4,6 +4,9 @@ use config::MissedBlockThreshold;
 use crypto::account;
 use futures::StreamExt;
 use logger::prelude::*;
+use metrics::trace::KeyValue;
+use notifier::status::{BlockSignature, CheckerStatuses, MissedBlockWindow};
+use notifier::{Alert, AlertKind, AlertSender};
 use std::sync::mpsc::SyncSender;
 
 #[derive(Debug)]
@@ -21,79 +24,160 @@ impl From<BlockMessage> for MissedBlockMessage {
 pub struct MissedBlockChecker {
     validator_account: account::Id,
     missed_block_threshold: MissedBlockThreshold,
+    teritorid_endpoint: String,
     receiver: Receiver<MissedBlockMessage>,
+    alert_sender: AlertSender,
+    statuses: CheckerStatuses,
+    missed_block_heights: Vec<i64>,
 }
 
 impl MissedBlockChecker {
     pub fn new(
         validator_account: account::Id,
         missed_block_threshold: MissedBlockThreshold,
+        teritorid_endpoint: String,
         receiver: Receiver<MissedBlockMessage>,
+        alert_sender: AlertSender,
+        statuses: CheckerStatuses,
     ) -> Self {
         Self {
             validator_account,
             missed_block_threshold,
+            teritorid_endpoint,
             receiver,
+            alert_sender,
+            statuses,
+            missed_block_heights: vec![],
         }
     }
 
-    pub async fn run(mut self) {
-        let mut missed_block_heights = vec![];
+    async fn check(&mut self, message: BlockMessage) {
         let missed_block_threshold = self.missed_block_threshold;
         let validator_address = self.validator_account.clone().to_string();
         let validator_address_bytes = self.validator_account.as_bytes();
-        while let Some(message) = self.receiver.next().await {
-            match message {
-                MissedBlockMessage::Check(message) => {
-                    if let Some(block) = message.block.as_ref() {
-                        if let Some(commit) = block.last_commit.as_ref() {
-                            let block_height = block.header.as_ref().unwrap().height;
-                            let signed = commit
-                                .signatures
-                                .iter()
-                                .find(|s| {
-                                    trace!(
-                                        "signature: {} detected!",
-                                        account::Id::try_from(s.validator_address.clone()).unwrap()
-                                    );
-                                    s.validator_address.as_slice() == validator_address_bytes
-                                })
-                                .is_some();
+        let labels = [self.teritorid_endpoint.as_str(), validator_address.as_str()];
+        if let Some(block) = message.block.as_ref() {
+            if let Some(commit) = block.last_commit.as_ref() {
+                let block_height = block.header.as_ref().unwrap().height;
+                let signed = commit
+                    .signatures
+                    .iter()
+                    .find(|s| {
+                        trace!(
+                            "signature: {} detected!",
+                            account::Id::try_from(s.validator_address.clone()).unwrap()
+                        );
+                        s.validator_address.as_slice() == validator_address_bytes
+                    })
+                    .is_some();
 
-                            let lowest =
-                                block_height - (missed_block_threshold.denominator as i64) + 1;
-                            missed_block_heights = missed_block_heights
-                                .clone()
-                                .into_iter()
-                                .filter(|&h| h >= lowest)
-                                .collect();
+                let lowest = block_height - (missed_block_threshold.denominator as i64) + 1;
+                self.missed_block_heights = self
+                    .missed_block_heights
+                    .clone()
+                    .into_iter()
+                    .filter(|&h| h >= lowest)
+                    .collect();
 
-                            if signed {
-                                info!(
-                                    "{} has signed for block {}",
-                                    validator_address.as_str(),
-                                    block_height
-                                )
-                            } else {
-                                missed_block_heights.push(block_height);
-                                if missed_block_heights.len()
-                                    >= missed_block_threshold.numerator as usize
-                                {
-                                    error!(
-                                        "{} has not signed for block {}",
-                                        validator_address.as_str(),
-                                        block_height
-                                    )
-                                } else {
-                                    warn!(
-                                        "{} has not signed for block {} but under threshold",
-                                        validator_address.as_str(),
-                                        block_height
-                                    )
-                                }
-                            }
-                        }
+                self.alert_sender
+                    .report_signature(self.teritorid_endpoint.as_str(), signed);
+                if signed {
+                    info!(
+                        "{} has signed for block {}",
+                        validator_address.as_str(),
+                        block_height
+                    );
+                    if self.missed_block_heights.len() < missed_block_threshold.numerator as usize {
+                        let mut alert = Alert::resolved(
+                            AlertKind::MissedBlock,
+                            self.teritorid_endpoint.as_str(),
+                            format!(
+                                "{} has signed for block {}",
+                                validator_address.as_str(),
+                                block_height
+                            ),
+                        );
+                        alert
+                            .validator(validator_address.as_str())
+                            .height(block_height)
+                            .field("missed_blocks", self.missed_block_heights.len())
+                            .field("window", missed_block_threshold.denominator);
+                        self.alert_sender.emit(alert);
                     }
+                } else {
+                    self.missed_block_heights.push(block_height);
+                    if self.missed_block_heights.len() >= missed_block_threshold.numerator as usize
+                    {
+                        let mut alert = Alert::new(
+                            AlertKind::MissedBlock,
+                            self.teritorid_endpoint.as_str(),
+                            format!(
+                                "{} has not signed for block {}",
+                                validator_address.as_str(),
+                                block_height
+                            ),
+                        );
+                        alert
+                            .validator(validator_address.as_str())
+                            .height(block_height)
+                            .field("missed_blocks", self.missed_block_heights.len())
+                            .field("window", missed_block_threshold.denominator);
+                        self.alert_sender.emit(alert);
+                    } else {
+                        warn!(
+                            "{} has not signed for block {} but under threshold",
+                            validator_address.as_str(),
+                            block_height
+                        )
+                    }
+                }
+                metrics::MISSED_BLOCKS
+                    .with_label_values(&labels)
+                    .set(self.missed_block_heights.len() as i64);
+                self.statuses.record_signature(
+                    self.teritorid_endpoint.as_str(),
+                    BlockSignature {
+                        height: block_height,
+                        signed,
+                    },
+                );
+                self.statuses.set_missed_blocks(
+                    self.teritorid_endpoint.as_str(),
+                    MissedBlockWindow {
+                        from_height: lowest,
+                        to_height: block_height,
+                        threshold: missed_block_threshold.numerator,
+                        missed_heights: self.missed_block_heights.clone(),
+                    },
+                );
+                self.statuses.checked(
+                    self.teritorid_endpoint.as_str(),
+                    AlertKind::MissedBlock.as_str(),
+                );
+            }
+        }
+    }
+
+    pub async fn run(mut self) {
+        let validator_address = self.validator_account.clone().to_string();
+        let labels = [self.teritorid_endpoint.as_str(), validator_address.as_str()];
+        metrics::MISSED_BLOCKS_WINDOW
+            .with_label_values(&labels)
+            .set(self.missed_block_threshold.denominator as i64);
+        while let Some(message) = self.receiver.next().await {
+            match message {
+                MissedBlockMessage::Check(message) => {
+                    let attributes = vec![
+                        KeyValue::new("endpoint", self.teritorid_endpoint.clone()),
+                        KeyValue::new("validator", validator_address.clone()),
+                        KeyValue::new("height", message.height()),
+                    ];
+                    metrics::trace::in_span(
+                        "MissedBlockChecker::check",
+                        attributes,
+                        self.check(message),
+                    )
+                    .await;
                 }
                 MissedBlockMessage::Terminate(sender) => {
                     info!("missed block checker will be terminated soon...");
@@ -1,6 +1,9 @@
 use channel::Receiver;
 use futures::StreamExt;
 use logger::prelude::*;
+use metrics::trace::KeyValue;
+use notifier::status::CheckerStatuses;
+use notifier::{Alert, AlertKind, AlertSender};
 use std::sync::mpsc::SyncSender;
 
 #[derive(Debug)]
@@ -19,6 +22,8 @@ pub struct SlashesChecker {
     validator_address: String,
     teritorid_endpoint: String,
     receiver: Receiver<SlashesMessage>,
+    alert_sender: AlertSender,
+    statuses: CheckerStatuses,
 }
 
 impl SlashesChecker {
@@ -26,48 +31,100 @@ impl SlashesChecker {
         validator_address: String,
         teritorid_endpoint: String,
         receiver: Receiver<SlashesMessage>,
+        alert_sender: AlertSender,
+        statuses: CheckerStatuses,
     ) -> Self {
         Self {
             validator_address,
             teritorid_endpoint,
             receiver,
+            alert_sender,
+            statuses,
         }
     }
+    async fn check(&mut self, params: SlashesMessageParams) {
+        match teritoricli::lock_client(&self.teritorid_endpoint)
+            .await
+            .fetch_slashes(
+                self.validator_address.clone(),
+                params.starting_height,
+                params.ending_height,
+            )
+            .await
+        {
+            Ok(slash_events) => {
+                metrics::SLASH_EVENTS
+                    .with_label_values(&[
+                        self.teritorid_endpoint.as_str(),
+                        self.validator_address.as_str(),
+                    ])
+                    .inc_by(slash_events.len() as u64);
+                self.statuses.checked(
+                    self.teritorid_endpoint.as_str(),
+                    AlertKind::Slashes.as_str(),
+                );
+                if !slash_events.is_empty() {
+                    let mut alert = Alert::new(
+                        AlertKind::Slashes,
+                        self.teritorid_endpoint.as_str(),
+                        format!(
+                            "validator {} has slash event between {} and {}",
+                            self.validator_address.as_str(),
+                            params.starting_height,
+                            params.ending_height,
+                        ),
+                    );
+                    alert
+                        .validator(self.validator_address.as_str())
+                        .height(params.ending_height as i64)
+                        .field("starting_height", params.starting_height)
+                        .field("ending_height", params.ending_height)
+                        .field("slash_events", slash_events.len());
+                    self.alert_sender.emit(alert);
+                } else {
+                    info!(
+                        "validator {} has no slash event between {} and {}",
+                        self.validator_address.as_str(),
+                        params.starting_height,
+                        params.ending_height,
+                    );
+                }
+            }
+            Err(err) => {
+                let mut alert = Alert::new(
+                    AlertKind::CheckFailure,
+                    self.teritorid_endpoint.as_str(),
+                    err.to_string(),
+                );
+                alert
+                    .validator(self.validator_address.as_str())
+                    .field("check", AlertKind::Slashes);
+                self.alert_sender.emit(alert);
+            }
+        }
+    }
+
     pub async fn run(mut self) {
+        // exported as 0 until the first slash event.
+        metrics::SLASH_EVENTS.with_label_values(&[
+            self.teritorid_endpoint.as_str(),
+            self.validator_address.as_str(),
+        ]);
         while let Some(message) = self.receiver.next().await {
             match message {
                 SlashesMessage::Check(params) => {
-                    match teritoricli::get_client(&self.teritorid_endpoint)
-                        .lock()
-                        .await
-                        .fetch_slashes(
-                            self.validator_address.clone(),
-                            params.starting_height,
-                            params.ending_height,
-                        )
-                        .await
-                    {
-                        Ok(slash_events) => {
-                            if slash_events.len() > 0 {
-                                error!(
-                                    "validator {} has slash event between {} and {}",
-                                    self.validator_address.as_str(),
-                                    params.starting_height,
-                                    params.ending_height,
-                                );
-                            } else {
-                                info!(
-                                    "validator {} has no slash event between {} and {}",
-                                    self.validator_address.as_str(),
-                                    params.starting_height,
-                                    params.ending_height,
-                                );
-                            }
-                        }
-                        Err(err) => {
-                            error!("{}", err);
-                        }
-                    }
+                    let attributes = vec![
+                        KeyValue::new("endpoint", self.teritorid_endpoint.clone()),
+                        KeyValue::new("validator", self.validator_address.clone()),
+                        KeyValue::new("starting_height", params.starting_height as i64),
+                        KeyValue::new("ending_height", params.ending_height as i64),
+                    ];
+                    metrics::trace::in_span(
+                        "SlashesChecker::check",
+                        attributes,
+                        self.check(params),
+                    )
+                    .await;
                 }
                 SlashesMessage::Terminate(sender) => {
                     info!("slashes checker will be terminated soon...");
@@ -1,6 +1,9 @@
 use channel::Receiver;
 use futures::StreamExt;
 use logger::prelude::*;
+use metrics::trace::KeyValue;
+use notifier::status::{CheckerStatuses, ValidatorStatus};
+use notifier::{Alert, AlertKind, AlertSender};
 use std::sync::mpsc::SyncSender;
 
 const BOND_STATUS_BONDED: i32 = 3;
@@ -15,6 +18,8 @@ pub struct ValidatorStatusChecker {
     validator_address: String,
     teritorid_endpoint: String,
     receiver: Receiver<ValidatorStatusMessage>,
+    alert_sender: AlertSender,
+    statuses: CheckerStatuses,
 }
 
 impl ValidatorStatusChecker {
@@ -22,56 +27,115 @@ impl ValidatorStatusChecker {
         validator_address: String,
         teritorid_endpoint: String,
         receiver: Receiver<ValidatorStatusMessage>,
+        alert_sender: AlertSender,
+        statuses: CheckerStatuses,
     ) -> Self {
         Self {
             validator_address,
             teritorid_endpoint,
             receiver,
+            alert_sender,
+            statuses,
         }
     }
+    async fn check(&mut self) {
+        match teritoricli::lock_client(&self.teritorid_endpoint)
+            .await
+            .fetch_validator_status(self.validator_address.clone())
+            .await
+        {
+            Ok(validator) => {
+                self.statuses.checked(
+                    self.teritorid_endpoint.as_str(),
+                    AlertKind::ValidatorStatus.as_str(),
+                );
+                if let Some(validator) = validator {
+                    let labels = [
+                        self.teritorid_endpoint.as_str(),
+                        self.validator_address.as_str(),
+                    ];
+                    metrics::VALIDATOR_JAILED
+                        .with_label_values(&labels)
+                        .set(validator.jailed as i64);
+                    metrics::VALIDATOR_BOND_STATUS
+                        .with_label_values(&labels)
+                        .set(validator.status as i64);
+                    self.statuses.set_validator(
+                        self.teritorid_endpoint.as_str(),
+                        ValidatorStatus {
+                            status: validator.status,
+                            jailed: validator.jailed,
+                        },
+                    );
+                    let mut has_error = false;
+                    if validator.jailed {
+                        has_error = true;
+                    }
+                    if validator.status != BOND_STATUS_BONDED {
+                        has_error = true;
+                    }
+                    if has_error {
+                        let mut alert = Alert::new(
+                            AlertKind::ValidatorStatus,
+                            self.teritorid_endpoint.as_str(),
+                            format!(
+                                "validator {} is not healthy...",
+                                self.validator_address.as_str()
+                            ),
+                        );
+                        alert
+                            .validator(self.validator_address.as_str())
+                            .field("jailed", validator.jailed)
+                            .field("status", validator.status);
+                        self.alert_sender.emit(alert);
+                    } else {
+                        info!("validator {} is healthy.", self.validator_address.as_str());
+                        let mut alert = Alert::resolved(
+                            AlertKind::ValidatorStatus,
+                            self.teritorid_endpoint.as_str(),
+                            format!("validator {} is healthy.", self.validator_address.as_str()),
+                        );
+                        alert
+                            .validator(self.validator_address.as_str())
+                            .field("jailed", validator.jailed)
+                            .field("status", validator.status);
+                        self.alert_sender.emit(alert);
+                    }
+                } else {
+                    warn!(
+                        "validator response is none for {}",
+                        self.validator_address.as_str()
+                    );
+                }
+            }
+            Err(err) => {
+                let mut alert = Alert::new(
+                    AlertKind::CheckFailure,
+                    self.teritorid_endpoint.as_str(),
+                    err.to_string(),
+                );
+                alert
+                    .validator(self.validator_address.as_str())
+                    .field("check", AlertKind::ValidatorStatus);
+                self.alert_sender.emit(alert);
+            }
+        }
+    }
+
     pub async fn run(mut self) {
         while let Some(message) = self.receiver.next().await {
             match message {
                 ValidatorStatusMessage::Check => {
-                    match teritoricli::get_client(&self.teritorid_endpoint)
-                        .lock()
-                        .await
-                        .fetch_validator_status(self.validator_address.clone())
-                        .await
-                    {
-                        Ok(validator) => {
-                            if let Some(validator) = validator {
-                                let mut has_error = false;
-                                if validator.jailed {
-                                    has_error = true;
-                                }
-                                if validator.status != BOND_STATUS_BONDED {
-                                    has_error = true;
-                                }
-                                if has_error {
-                                    error!(
-                                        jailed = validator.jailed,
-                                        status = validator.status,
-                                        "validator {} is not healthy...",
-                                        self.validator_address.as_str()
-                                    )
-                                } else {
-                                    info!(
-                                        "validator {} is healthy.",
-                                        self.validator_address.as_str()
-                                    );
-                                }
-                            } else {
-                                warn!(
-                                    "validator response is none for {}",
-                                    self.validator_address.as_str()
-                                );
-                            }
-                        }
-                        Err(err) => {
-                            error!("{}", err);
-                        }
-                    }
+                    let attributes = vec![
+                        KeyValue::new("endpoint", self.teritorid_endpoint.clone()),
+                        KeyValue::new("validator", self.validator_address.clone()),
+                    ];
+                    metrics::trace::in_span(
+                        "ValidatorStatusChecker::check",
+                        attributes,
+                        self.check(),
+                    )
+                    .await;
                 }
                 ValidatorStatusMessage::Terminate(sender) => {
                     info!("validator status checker will be terminated soon...");
@@ -0,0 +1,75 @@
+use crate::Result;
+use anyhow::{anyhow, Context};
+use config::configs::ApplicationConfig;
+use serde_json::Value;
+use std::net::{IpAddr, Ipv4Addr};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(about = "List and acknowledge active alerts of the running teritorimon")]
+pub enum AlertsCommand {
+    #[structopt(about = "List firing conditions and escalating events")]
+    List,
+    #[structopt(about = "Stop reminders and escalation of an alert until it is resolved")]
+    Ack { id: String },
+}
+
+impl AlertsCommand {
+    pub fn run(self, app_config: &ApplicationConfig) -> Result<()> {
+        let api = app_config
+            .api
+            .as_ref()
+            .ok_or_else(|| anyhow!("the http api is not enabled, see [api] of the config..."))?;
+        let mut listen = api.get_listen();
+        if listen.ip().is_unspecified() {
+            listen.set_ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        }
+        let base_url = format!("http://{}/api/v1", listen);
+        let client = reqwest::blocking::Client::new();
+        match self {
+            Self::List => {
+                let alerts: Vec<Value> = client
+                    .get(format!("{}/alerts", base_url))
+                    .send()
+                    .with_context(|| format!("failed to connect to {}", base_url))?
+                    .error_for_status()?
+                    .json()?;
+                for alert in alerts {
+                    let field = |key: &str| alert[key].as_str().unwrap_or_default().to_owned();
+                    println!(
+                        "{}\t{}\t[{}] {}\tsince: {}\tvalidator: {}\tendpoint: {}\t{}",
+                        field("id"),
+                        if alert["acknowledged"].as_bool().unwrap_or_default() {
+                            "acked"
+                        } else {
+                            "-"
+                        },
+                        field("severity"),
+                        field("kind"),
+                        alert["started_at"]
+                            .as_str()
+                            .unwrap_or(field("timestamp").as_str()),
+                        alert["validator"].as_str().unwrap_or("*"),
+                        field("endpoint"),
+                        field("message"),
+                    );
+                }
+            }
+            Self::Ack { id } => {
+                let response = client
+                    .post(format!("{}/alerts/{}/ack", base_url, id))
+                    .send()
+                    .with_context(|| format!("failed to connect to {}", base_url))?;
+                if !response.status().is_success() {
+                    let body: Value = response.json().unwrap_or_default();
+                    return Err(anyhow!(
+                        "failed to acknowledge alert: {}",
+                        body["error"].as_str().unwrap_or_default()
+                    ));
+                }
+                println!("alert {} has been acknowledged", id);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,443 @@
+use channel::Receiver;
+use chrono::Utc;
+use futures::future::join_all;
+use futures::StreamExt;
+use logger::prelude::*;
+use notifier::active::{ActiveAlert, ActiveAlerts};
+use notifier::digest::Digest;
+use notifier::escalation::Escalator;
+use notifier::history::{HistoryWriter, Outcome};
+use notifier::hook::HookRunner;
+use notifier::inhibit::Inhibitor;
+use notifier::lifecycle::AlertLifecycle;
+use notifier::queue::NotifierQueue;
+use notifier::route::Route;
+use notifier::silence::Silencer;
+use notifier::status::CheckerStatuses;
+use notifier::template::MessageTemplates;
+use notifier::throttle::RateLimiter;
+use notifier::{Alert, AlertMessage, Notifier};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+/// how often to check escalations and digests due.
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+/// how long to wait for the queued alerts to be delivered on termination.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// receives alerts emitted by checkers and fans them out to every registered notifier.
+pub struct AlertDispatcher {
+    receiver: Receiver<AlertMessage>,
+    notifiers: Vec<NotifierQueue>,
+    endpoint_notifiers: HashMap<String, Vec<NotifierQueue>>,
+    receivers: HashMap<String, Vec<NotifierQueue>>,
+    deliveries: Vec<JoinHandle<()>>,
+    route: Option<Route>,
+    lifecycle: AlertLifecycle,
+    rate_limiter: Option<RateLimiter>,
+    silencer: Silencer,
+    inhibitor: Inhibitor,
+    templates: MessageTemplates,
+    escalator: Escalator,
+    hooks: Option<HookRunner>,
+    active_alerts: ActiveAlerts,
+    statuses: CheckerStatuses,
+    digests: Vec<Digest>,
+    history: Option<HistoryWriter>,
+}
+
+impl AlertDispatcher {
+    pub fn new(receiver: Receiver<AlertMessage>) -> Self {
+        Self {
+            receiver,
+            notifiers: Vec::new(),
+            endpoint_notifiers: HashMap::new(),
+            receivers: HashMap::new(),
+            deliveries: Vec::new(),
+            route: None,
+            lifecycle: AlertLifecycle::new(),
+            rate_limiter: None,
+            silencer: Silencer::new(),
+            inhibitor: Inhibitor::new(),
+            templates: MessageTemplates::new(),
+            escalator: Escalator::new(),
+            hooks: None,
+            active_alerts: ActiveAlerts::new(),
+            statuses: CheckerStatuses::new(),
+            digests: Vec::new(),
+            history: None,
+        }
+    }
+
+    /// where to store the alerts handled.
+    pub fn history(&mut self, history: HistoryWriter) -> &mut Self {
+        self.history = Some(history);
+        self
+    }
+
+    pub fn add_digest(&mut self, digest: Digest) -> &mut Self {
+        self.digests.push(digest);
+        self
+    }
+
+    /// where to publish the active alerts to.
+    pub fn active_alerts(&mut self, active_alerts: ActiveAlerts) -> &mut Self {
+        self.active_alerts = active_alerts;
+        self
+    }
+
+    /// where to report the latest alerts of each checker to.
+    pub fn statuses(&mut self, statuses: CheckerStatuses) -> &mut Self {
+        self.statuses = statuses;
+        self
+    }
+
+    pub fn escalator(&mut self, escalator: Escalator) -> &mut Self {
+        self.escalator = escalator;
+        self
+    }
+
+    pub fn hooks(&mut self, hooks: HookRunner) -> &mut Self {
+        self.hooks = Some(hooks);
+        self
+    }
+
+    pub fn templates(&mut self, templates: MessageTemplates) -> &mut Self {
+        self.templates = templates;
+        self
+    }
+
+    pub fn inhibitor(&mut self, inhibitor: Inhibitor) -> &mut Self {
+        self.inhibitor = inhibitor;
+        self
+    }
+
+    pub fn silencer(&mut self, silencer: Silencer) -> &mut Self {
+        self.silencer = silencer;
+        self
+    }
+
+    pub fn lifecycle(&mut self, lifecycle: AlertLifecycle) -> &mut Self {
+        self.lifecycle = lifecycle;
+        self
+    }
+
+    pub fn rate_limiter(&mut self, rate_limiter: RateLimiter) -> &mut Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    pub fn add_notifier(&mut self, notifier: Box<dyn Notifier>) -> &mut Self {
+        self.notifiers.push(NotifierQueue::new(notifier));
+        self
+    }
+
+    /// registers a notifier which only receives alerts about the specific teritori daemon endpoint.
+    pub fn add_endpoint_notifier(
+        &mut self,
+        endpoint: &str,
+        notifier: Box<dyn Notifier>,
+    ) -> &mut Self {
+        self.endpoint_notifiers
+            .entry(endpoint.to_owned())
+            .or_default()
+            .push(NotifierQueue::new(notifier));
+        self
+    }
+
+    /// registers a notifier of the named receiver which routes deliver alerts to.
+    pub fn add_receiver_notifier(
+        &mut self,
+        receiver: &str,
+        notifier: Box<dyn Notifier>,
+    ) -> &mut Self {
+        self.receivers
+            .entry(receiver.to_owned())
+            .or_default()
+            .push(NotifierQueue::new(notifier));
+        self
+    }
+
+    pub fn route(&mut self, route: Route) -> &mut Self {
+        self.route = Some(route);
+        self
+    }
+
+    pub async fn run(mut self) {
+        self.start_deliveries();
+        let mut ticker = tokio::time::interval(TICK_INTERVAL);
+        loop {
+            tokio::select! {
+                message = self.receiver.next() => match message {
+                    Some(AlertMessage::Alert(alert)) => self.process(alert),
+                    Some(AlertMessage::Signature { endpoint, signed }) => {
+                        for digest in &mut self.digests {
+                            digest.record_signature(endpoint.as_str(), signed);
+                        }
+                    }
+                    Some(AlertMessage::Acknowledge(id)) => self.acknowledge(id.as_str()),
+                    Some(AlertMessage::Terminate(sender)) => {
+                        info!("alert dispatcher will be terminated soon...");
+                        self.finish_deliveries().await;
+                        // the alerts handled must be stored before the process exits.
+                        if let Some(history) = self.history.take() {
+                            history.close();
+                        }
+                        let _ = sender.send(());
+                        break;
+                    }
+                    None => break,
+                },
+                _ = ticker.tick() => {
+                    // the firing alerts silenced are notified of once the silences end.
+                    for dedup_key in self.silencer.ended(Utc::now()) {
+                        self.lifecycle.renotify(dedup_key.as_str());
+                    }
+                    self.escalate();
+                    self.send_digests();
+                    if let Some(hooks) = self.hooks.as_mut() {
+                        hooks.run_due(Instant::now());
+                    }
+                }
+            }
+            self.publish();
+        }
+    }
+
+    fn start_deliveries(&mut self) {
+        let queues = self
+            .notifiers
+            .iter_mut()
+            .chain(self.endpoint_notifiers.values_mut().flatten())
+            .chain(self.receivers.values_mut().flatten());
+        for queue in queues {
+            if let Some(handle) = queue.start() {
+                self.deliveries.push(handle);
+            }
+        }
+    }
+
+    /// waits until the queued alerts are delivered, unless the notifiers are stuck.
+    async fn finish_deliveries(&mut self) {
+        self.notifiers.clear();
+        self.endpoint_notifiers.clear();
+        self.receivers.clear();
+        let deliveries = std::mem::take(&mut self.deliveries);
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, join_all(deliveries))
+            .await
+            .is_err()
+        {
+            warn!("some alerts were not delivered in {:?}", SHUTDOWN_TIMEOUT);
+        }
+    }
+
+    fn acknowledge(&mut self, id: &str) {
+        let dedup_key = match self.active_alerts.get(id) {
+            Some(active) => active.alert.dedup_key(),
+            None => {
+                warn!("alert to acknowledge is not active: {}", id);
+                return;
+            }
+        };
+        // both may be false if it has been resolved in the meantime.
+        let reminding = self.lifecycle.acknowledge(dedup_key.as_str());
+        let escalating = self.escalator.acknowledge(dedup_key.as_str());
+        if reminding || escalating {
+            info!("alert {} ({}) is acknowledged", id, dedup_key);
+        }
+    }
+
+    /// publishes the firing conditions and escalating events.
+    fn publish(&self) {
+        let mut alerts: Vec<ActiveAlert> = self
+            .lifecycle
+            .firing()
+            .map(|alert| ActiveAlert {
+                alert: alert.clone(),
+                acknowledged: self.lifecycle.is_acknowledged(alert.dedup_key().as_str()),
+            })
+            .collect();
+        alerts.extend(self.escalator.events().map(|alert| ActiveAlert {
+            alert: alert.clone(),
+            acknowledged: false,
+        }));
+        self.active_alerts.replace(alerts);
+    }
+
+    fn process(&mut self, alert: Alert) {
+        // checkers report conditions on every check, only the transitions are delivered.
+        let alert = match self.lifecycle.observe(alert) {
+            Some(alert) => alert,
+            None => return,
+        };
+        self.statuses
+            .record_alert(alert.endpoint.as_str(), alert.to_json());
+        // digests report what happened, even if it is not delivered.
+        for digest in &mut self.digests {
+            digest.record(&alert);
+        }
+        // the alerts inhibited by the resolved one are notified of if they are still firing.
+        if alert.is_resolved() {
+            for dedup_key in self.inhibitor.released_by(alert.dedup_key().as_str()) {
+                self.lifecycle.renotify(dedup_key.as_str());
+            }
+        }
+        // resolutions stop escalating and running hooks even if they are not delivered.
+        let escalated_receivers = if alert.is_resolved() {
+            if let Some(hooks) = self.hooks.as_mut() {
+                hooks.observe(&alert, Instant::now());
+            }
+            self.escalator.observe(&alert, Instant::now())
+        } else {
+            Vec::new()
+        };
+        if let Some(inhibited_by) = self.inhibitor.inhibited_by(&alert, self.lifecycle.firing()) {
+            info!(
+                kind = alert.kind.as_str(),
+                status = alert.status.as_str(),
+                "alert is inhibited by {}: {}",
+                inhibited_by,
+                alert.message.as_str()
+            );
+            self.record(&alert, Outcome::Inhibited);
+            return;
+        }
+        if let Some(silenced_by) = self.silencer.silenced_by(&alert) {
+            info!(
+                kind = alert.kind.as_str(),
+                status = alert.status.as_str(),
+                "alert is silenced by {}: {}",
+                silenced_by,
+                alert.message.as_str()
+            );
+            self.record(&alert, Outcome::Silenced);
+            return;
+        }
+        if self.is_rate_limited(&alert) {
+            self.record(&alert, Outcome::RateLimited);
+            return;
+        }
+        self.record(&alert, Outcome::Delivered);
+        if !alert.is_resolved() {
+            self.escalator.observe(&alert, Instant::now());
+            if let Some(hooks) = self.hooks.as_mut() {
+                hooks.observe(&alert, Instant::now());
+            }
+        }
+        self.dispatch(&alert, escalated_receivers);
+    }
+
+    /// delivers unacknowledged alerts to the receivers of the escalation steps passed.
+    fn escalate(&mut self) {
+        for (alert, receiver) in self.escalator.due(Instant::now()) {
+            self.record(&alert, Outcome::Escalated);
+            self.deliver_to(Some(receiver.as_str()), &alert);
+        }
+    }
+
+    fn send_digests(&mut self) {
+        let now = Utc::now();
+        let mut reports = Vec::new();
+        for digest in &mut self.digests {
+            if let Some(report) = digest.due(now) {
+                reports.push((report, digest.receivers.clone()));
+            }
+        }
+        for (report, receivers) in reports {
+            self.record(&report, Outcome::Delivered);
+            if receivers.is_empty() {
+                self.deliver_to(None, &report);
+            }
+            for receiver in receivers {
+                self.deliver_to(Some(receiver.as_str()), &report);
+            }
+        }
+    }
+
+    fn record(&self, alert: &Alert, outcome: Outcome) {
+        if let Some(history) = self.history.as_ref() {
+            history.record(alert, outcome);
+        }
+    }
+
+    /// delivers the alert to the notifiers of the receiver, or to the global notifiers if `None`.
+    fn deliver_to(&self, receiver: Option<&str>, alert: &Alert) {
+        let notifiers = match receiver {
+            Some(receiver) => match self.receivers.get(receiver) {
+                Some(receiver_notifiers) => receiver_notifiers,
+                None => return,
+            },
+            None => &self.notifiers,
+        };
+        let alert = self.templates.render(receiver, alert);
+        for notifier in notifiers {
+            notifier.push(alert.clone());
+        }
+    }
+
+    /// resolutions are always delivered so that incidents opened in notifiers get closed.
+    fn is_rate_limited(&mut self, alert: &Alert) -> bool {
+        if alert.is_resolved() {
+            return false;
+        }
+        let rate_limiter = match self.rate_limiter.as_mut() {
+            Some(rate_limiter) => rate_limiter,
+            None => return false,
+        };
+        match rate_limiter.acquire(Instant::now()) {
+            Some(suppressed) => {
+                if suppressed > 0 {
+                    warn!(
+                        "{} alerts were dropped by the notification rate limit",
+                        suppressed
+                    );
+                }
+                false
+            }
+            None => {
+                warn!(
+                    "notification rate limit exceeded, {} alert is dropped: {}",
+                    alert.kind.as_str(),
+                    alert.message.as_str()
+                );
+                true
+            }
+        }
+    }
+
+    /// delivers the alert to the global, endpoint and routed notifiers,
+    /// and to the receivers it has been escalated to.
+    fn dispatch(&self, alert: &Alert, escalated_receivers: Vec<String>) {
+        let default_alert = self.templates.render(None, alert);
+        let mut deliveries: Vec<(&NotifierQueue, &Alert)> =
+            self.notifiers.iter().map(|n| (n, &default_alert)).collect();
+        if let Some(endpoint_notifiers) = self.endpoint_notifiers.get(alert.endpoint.as_str()) {
+            deliveries.extend(endpoint_notifiers.iter().map(|n| (n, &default_alert)));
+        }
+        // receivers may have their own templates.
+        let mut receiver_alerts = Vec::new();
+        let mut receivers = self
+            .route
+            .as_ref()
+            .map(|route| route.receivers(alert))
+            .unwrap_or_default();
+        for receiver in escalated_receivers {
+            if !receivers.contains(&receiver) {
+                receivers.push(receiver);
+            }
+        }
+        for receiver in receivers {
+            if let Some(receiver_notifiers) = self.receivers.get(receiver.as_str()) {
+                let receiver_alert = self.templates.render(Some(receiver.as_str()), alert);
+                receiver_alerts.push((receiver_notifiers, receiver_alert));
+            }
+        }
+        for (receiver_notifiers, receiver_alert) in &receiver_alerts {
+            deliveries.extend(receiver_notifiers.iter().map(|n| (n, receiver_alert)));
+        }
+        for (notifier, alert) in deliveries {
+            notifier.push(alert.clone());
+        }
+    }
+}
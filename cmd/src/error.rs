@@ -0,0 +1,16 @@
+use futures::channel::mpsc::SendError;
+use thiserror::Error as ThisError;
+
+/// why a tick of the check manager has failed.
+#[derive(ThisError, Debug)]
+pub enum CheckError {
+    /// the teritori daemon has not responded to a grpc request.
+    #[error("{cause}")]
+    Unreachable { cause: anyhow::Error },
+    /// a checker has not processed the messages handed over yet.
+    #[error("could not hand a message over to the {checker} checker: {cause}")]
+    Dispatch {
+        checker: &'static str,
+        cause: SendError,
+    },
+}
@@ -0,0 +1,92 @@
+use crate::Result;
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Utc};
+use config::configs::ApplicationConfig;
+use notifier::history::{AlertHistory, HistoryQuery};
+use notifier::AlertKind;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(about = "Look back on the alerts stored in the history database")]
+pub struct HistoryCommand {
+    #[structopt(
+        long,
+        help = "Only alerts since the time in RFC 3339 or the duration ago (e.g. 24h)"
+    )]
+    since: Option<String>,
+    #[structopt(
+        long,
+        help = "Only alerts before the time in RFC 3339 or the duration ago (e.g. 1h)"
+    )]
+    until: Option<String>,
+    #[structopt(long, help = "Validator address")]
+    validator: Option<String>,
+    #[structopt(
+        long,
+        help = "Teritori daemon grpc endpoint (e.g. http://127.0.0.1:9090)"
+    )]
+    endpoint: Option<String>,
+    #[structopt(
+        long = "kind",
+        help = "Alert kind (e.g. missed_block), can be repeated"
+    )]
+    kinds: Vec<String>,
+    #[structopt(long, default_value = "100", help = "The number of the latest alerts")]
+    limit: u32,
+    #[structopt(long, help = "Print every alert as a line of json")]
+    json: bool,
+}
+
+impl HistoryCommand {
+    pub fn run(self, app_config: &ApplicationConfig) -> Result<()> {
+        let history = app_config.history.as_ref().ok_or_else(|| {
+            anyhow!("the alert history is not enabled, see [history] of the config...")
+        })?;
+        let mut query = HistoryQuery {
+            since: self.since.as_deref().map(parse_time).transpose()?,
+            until: self.until.as_deref().map(parse_time).transpose()?,
+            validator: self.validator,
+            endpoint: self.endpoint,
+            kinds: Vec::new(),
+            limit: Some(self.limit),
+        };
+        for kind in self.kinds {
+            query
+                .kinds
+                .push(kind.parse::<AlertKind>()?.as_str().to_owned());
+        }
+        let history = AlertHistory::open(history.path.as_str())?;
+        for record in history.query(&query)? {
+            if self.json {
+                println!("{}", record.to_json());
+                continue;
+            }
+            println!(
+                "{}\t{}\t[{}] {}\t{}\tvalidator: {}\tendpoint: {}\theight: {}\t{}",
+                record.timestamp.to_rfc3339(),
+                record.status,
+                record.severity,
+                record.kind,
+                record.outcome,
+                record.validator.as_deref().unwrap_or("*"),
+                record.endpoint,
+                record
+                    .height
+                    .map(|height| height.to_string())
+                    .unwrap_or_else(|| "-".to_owned()),
+                record.message.replace('\n', " "),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// a time in RFC 3339, or the duration ago.
+fn parse_time(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let duration = duration_str::parse(value)
+        .with_context(|| format!("illegal time, neither RFC 3339 nor duration: {}", value))?;
+    Ok(Utc::now() - chrono::Duration::from_std(duration)?)
+}
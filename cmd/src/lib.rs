@@ -1,4 +1,9 @@
-use anyhow::anyhow;
+pub mod alerts;
+mod dispatcher;
+mod error;
+pub mod history;
+pub mod silence;
+
 use channel::Sender;
 use checker;
 use checker::is_syncing::IsSyncingMessage;
@@ -7,18 +12,191 @@ use checker::missed_block::MissedBlockMessage;
 use checker::new_proposal::NewProposalMessage;
 use checker::slashes::{SlashesMessage, SlashesMessageParams};
 use checker::validator_status::ValidatorStatusMessage;
+use chrono::Utc;
 use config::MissedBlockThreshold;
 use crypto::account;
+use dispatcher::AlertDispatcher;
+use error::CheckError;
 use logger::prelude::*;
+use metrics::trace::KeyValue;
+use notifier::active::ActiveAlerts;
+use notifier::digest::Digest;
+use notifier::escalation::{EscalationPolicy, Escalator};
+use notifier::history::{AlertHistory, HistoryWriter};
+use notifier::hook::{Hook, HookRunner};
+use notifier::inhibit::{InhibitRule, Inhibitor};
+use notifier::lifecycle::AlertLifecycle;
+use notifier::log::LogNotifier;
+use notifier::route::Route;
+use notifier::silence::{RecurringSilence, SilenceFile, Silencer};
+use notifier::status::CheckerStatuses;
+use notifier::template::MessageTemplates;
+use notifier::throttle::RateLimiter;
+use notifier::{Alert, AlertKind, AlertSender};
+use server::{Health, Server};
+use std::cmp::max;
 use std::str::FromStr;
 use std::thread;
+use std::time::{Duration, Instant};
 use tokio::signal::unix::{signal, SignalKind};
 
 pub type Result<T> = anyhow::Result<T>;
 
+/// the lower bound of how long a tick may take before teritorimon is considered stuck.
+const MIN_STALE_AFTER: Duration = Duration::from_secs(30);
+
 pub fn start() -> Result<()> {
     let app_config = config::app_config();
 
+    let (sender, receiver) = channel::new(1_024);
+    let mut alert_sender = AlertSender::new(sender);
+    let mut dispatcher = AlertDispatcher::new(receiver);
+    let mut lifecycle = AlertLifecycle::new();
+    for kind in AlertKind::ALL {
+        lifecycle.repeat_interval(kind, app_config.alerting.get_repeat_interval(kind.as_str()));
+    }
+    dispatcher.lifecycle(lifecycle);
+    if let Some(rate_limit) = app_config.alerting.rate_limit {
+        dispatcher.rate_limiter(RateLimiter::new(
+            rate_limit,
+            app_config.alerting.get_rate_limit_period(),
+        ));
+    }
+    let mut silencer = Silencer::new();
+    for silence in &app_config.silences {
+        silencer.add_recurring(RecurringSilence::from_config(silence)?);
+    }
+    silencer.file(SilenceFile::load(
+        app_config.alerting.silences_file.as_str(),
+    )?);
+    dispatcher.silencer(silencer);
+    dispatcher.add_notifier(Box::new(LogNotifier));
+    for notifier in &app_config.notifiers {
+        dispatcher.add_notifier(notifier::new_notifier(notifier)?);
+    }
+    for checker in &app_config.checkers {
+        for notifier in &checker.notifiers {
+            dispatcher.add_endpoint_notifier(
+                checker.teritori_grpc_endpoint().as_str(),
+                notifier::new_notifier(notifier)?,
+            );
+        }
+    }
+    let mut templates = MessageTemplates::new();
+    templates
+        .vars(app_config.template_vars.clone())
+        .register(None, &app_config.templates)?;
+    if let Some(api) = app_config.api.as_ref() {
+        templates.ack_base_url(api.get_external_url().as_str());
+    }
+    for receiver in &app_config.receivers {
+        templates.register(Some(receiver.name.as_str()), &receiver.templates)?;
+        for notifier in &receiver.notifiers {
+            dispatcher
+                .add_receiver_notifier(receiver.name.as_str(), notifier::new_notifier(notifier)?);
+        }
+    }
+    dispatcher.templates(templates);
+    if let Some(route) = app_config.route.as_ref() {
+        dispatcher.route(Route::from_config(route));
+    }
+    let mut inhibitor = Inhibitor::new();
+    for inhibit_rule in &app_config.inhibit_rules {
+        inhibitor.add_rule(InhibitRule::from_config(inhibit_rule));
+    }
+    dispatcher.inhibitor(inhibitor);
+    let mut escalator = Escalator::new();
+    for escalation in &app_config.escalations {
+        escalator.add_policy(EscalationPolicy::from_config(escalation));
+    }
+    dispatcher.escalator(escalator);
+    if !app_config.hooks.is_empty() {
+        let mut hooks = HookRunner::new(app_config.alerting.hook_concurrency);
+        for hook in &app_config.hooks {
+            hooks.add_hook(Hook::from_config(hook));
+        }
+        dispatcher.hooks(hooks);
+    }
+    for digest_config in &app_config.digests {
+        let mut digest = Digest::from_config(digest_config, Utc::now());
+        for checker in &app_config.checkers {
+            let name = checker
+                .validator_address
+                .as_deref()
+                .or(checker.validator_account.as_deref())
+                .unwrap_or("-");
+            digest.add_validator(checker.teritori_grpc_endpoint().as_str(), name);
+        }
+        dispatcher.add_digest(digest);
+    }
+    if let Some(history) = app_config.history.as_ref() {
+        dispatcher.history(HistoryWriter::spawn(AlertHistory::open(
+            history.path.as_str(),
+        )?)?);
+    }
+    let alert_runtime = tokio::runtime::Builder::new_multi_thread()
+        .thread_name("alert")
+        .enable_all()
+        .build()
+        .expect("Failed to create tokio runtime!");
+    if let Some(tracing) = app_config.tracing.as_ref() {
+        // the spans are exported in the background of the alert runtime.
+        let _guard = alert_runtime.enter();
+        metrics::trace::install(
+            tracing.endpoint.as_str(),
+            tracing.service_name.as_str(),
+            tracing.get_timeout(),
+        )?;
+        info!("spans will be exported to {}", tracing.endpoint);
+    }
+    let active_alerts = ActiveAlerts::new();
+    dispatcher.active_alerts(active_alerts.clone());
+    let statuses = CheckerStatuses::new();
+    dispatcher.statuses(statuses.clone());
+    alert_runtime.spawn(dispatcher.run());
+    // teritorimon is considered stuck if a tick takes longer than this.
+    let stale_after = max(app_config.get_interval() * 3, MIN_STALE_AFTER);
+    let health = Health::new(stale_after);
+    for checker in &app_config.checkers {
+        health.add_endpoint(checker.teritori_grpc_endpoint().as_str());
+        statuses.register(
+            checker.teritori_grpc_endpoint().as_str(),
+            checker.validator_address.as_deref(),
+            checker.validator_account.as_deref(),
+        );
+    }
+    let mut servers: Vec<Server> = Vec::new();
+    if let Some(api) = app_config.api.as_ref() {
+        let mut server = Server::new(api.get_listen());
+        server
+            .alerts(active_alerts, alert_sender.clone())
+            .status(statuses.clone());
+        if api.dashboard {
+            server.dashboard();
+        }
+        servers.push(server);
+    }
+    if let Some(metrics) = app_config.metrics.as_ref() {
+        // shares the server with the api if listening on the same address.
+        match servers
+            .iter_mut()
+            .find(|s| s.listen() == metrics.get_listen())
+        {
+            Some(server) => {
+                server.metrics();
+            }
+            None => {
+                let mut server = Server::new(metrics.get_listen());
+                server.metrics();
+                servers.push(server);
+            }
+        }
+    }
+    for mut server in servers {
+        server.health(health.clone());
+        alert_runtime.spawn(server.run());
+    }
+
     let mut managers = Vec::new();
     let mut runtimes = Vec::new();
     for checker in &app_config.checkers {
@@ -29,8 +207,12 @@ pub fn start() -> Result<()> {
         if let Some(validator_address) = checker.validator_address.as_deref() {
             manager.validator_address(validator_address);
         }
+        let mut checker_alert_sender = alert_sender.clone();
+        checker_alert_sender.labels(checker.labels.clone());
         let runtime = manager
             .teritorid_endpoint(checker.teritori_grpc_endpoint().as_str())
+            .alert_sender(checker_alert_sender)
+            .statuses(statuses.clone())
             .check_if_syncing(checker.syncing)
             .check_if_new_proposal(checker.new_proposal)
             .check_if_missed_block(checker.missed_block, checker.missed_block_threshold)
@@ -67,6 +249,7 @@ pub fn start() -> Result<()> {
                     for manager in &mut managers {
                         manager.terminate();
                     }
+                    alert_sender.terminate();
                     break
                 }
                 _ = sigterm.recv() => {
@@ -74,24 +257,60 @@ pub fn start() -> Result<()> {
                     for manager in &mut managers {
                         manager.terminate();
                     }
+                    alert_sender.terminate();
                     break
                 }
                 _ = tick => {
                     for manager in &mut managers {
-                        if let Err(err) = manager.next().await {
-                            error!("{}", err);
-                        }
+                        let alert = match manager.next().await {
+                            Ok(manager) => {
+                                health.report(manager.teritorid_endpoint.as_str(), Instant::now(), None);
+                                Alert::resolved(
+                                    AlertKind::Unreachable,
+                                    manager.teritorid_endpoint.as_str(),
+                                    format!(
+                                        "the teritori daemon: {} is reachable",
+                                        manager.teritorid_endpoint.as_str()
+                                    ),
+                                )
+                            }
+                            Err(err @ CheckError::Unreachable { .. }) => {
+                                health.report(
+                                    manager.teritorid_endpoint.as_str(),
+                                    Instant::now(),
+                                    Some(err.to_string()),
+                                );
+                                Alert::new(
+                                    AlertKind::Unreachable,
+                                    manager.teritorid_endpoint.as_str(),
+                                    err.to_string(),
+                                )
+                            }
+                            // the daemon may be reachable, it is not a condition of the node.
+                            // (the endpoint gets stale if the checkers keep lagging behind)
+                            Err(err) => {
+                                error!("{}", err);
+                                continue;
+                            }
+                        };
+                        manager.emit(alert);
                     }
+                    health.tick(Instant::now());
                     sleep = true;
                 }
             }
         }
     });
+    if app_config.tracing.is_some() {
+        metrics::trace::shutdown();
+    }
     Ok(())
 }
 
 pub struct CheckManager {
     teritorid_endpoint: String,
+    alert_sender: Option<AlertSender>,
+    statuses: CheckerStatuses,
     validator_account: Option<account::Id>,
     validator_address: Option<String>,
     check_if_syncing: bool,
@@ -112,6 +331,8 @@ impl Default for CheckManager {
     fn default() -> Self {
         Self {
             teritorid_endpoint: "".to_owned(),
+            alert_sender: None,
+            statuses: CheckerStatuses::new(),
             validator_account: None,
             validator_address: None,
             check_if_syncing: true,
@@ -135,6 +356,14 @@ impl CheckManager {
         self.teritorid_endpoint = teritorid_endpoint.to_owned();
         self
     }
+    pub fn alert_sender(&mut self, alert_sender: AlertSender) -> &mut Self {
+        self.alert_sender = Some(alert_sender);
+        self
+    }
+    pub fn statuses(&mut self, statuses: CheckerStatuses) -> &mut Self {
+        self.statuses = statuses;
+        self
+    }
     pub fn validator_account(&mut self, validator_account: &str) -> &mut Self {
         let validator_account = account::Id::from_str(validator_account)
             .expect("validator account must be in valid hex string.");
@@ -170,17 +399,29 @@ impl CheckManager {
         self.check_if_slashes = check_if_slashes;
         self
     }
+    fn emit(&mut self, alert: Alert) {
+        if let Some(alert_sender) = self.alert_sender.as_mut() {
+            alert_sender.emit(alert);
+        }
+    }
     fn setup(&mut self) -> tokio::runtime::Runtime {
         let runtime = tokio::runtime::Builder::new_multi_thread()
             .thread_name(format!("{}", self.teritorid_endpoint.as_str()))
             .enable_all()
             .build()
             .expect("Failed to create tokio runtime!");
+        let alert_sender = self
+            .alert_sender
+            .as_ref()
+            .expect("alert sender must be provided to set up checkers.")
+            .clone();
         if self.check_if_syncing {
             let (sender, receiver) = channel::new(1_024);
             let checker = checker::is_syncing::IsSyncingChecker::new(
                 self.teritorid_endpoint.clone(),
                 receiver,
+                alert_sender.clone(),
+                self.statuses.clone(),
             );
             runtime.spawn(checker.run());
             self.is_syncing_checker = Some(sender);
@@ -190,6 +431,8 @@ impl CheckManager {
             let checker = checker::new_proposal::NewProposalChecker::new(
                 self.teritorid_endpoint.clone(),
                 receiver,
+                alert_sender.clone(),
+                self.statuses.clone(),
             );
             runtime.spawn(checker.run());
             self.new_proposal_checker = Some(sender);
@@ -207,7 +450,10 @@ impl CheckManager {
             let checker = checker::missed_block::MissedBlockChecker::new(
                 validator_account,
                 threshold,
+                self.teritorid_endpoint.clone(),
                 receiver,
+                alert_sender.clone(),
+                self.statuses.clone(),
             );
             runtime.spawn(checker.run());
             self.missed_block_checker = Some(sender);
@@ -223,6 +469,8 @@ impl CheckManager {
                 validator_address,
                 self.teritorid_endpoint.clone(),
                 receiver,
+                alert_sender.clone(),
+                self.statuses.clone(),
             );
             runtime.spawn(checker.run());
             self.validator_status_checker = Some(sender);
@@ -238,6 +486,8 @@ impl CheckManager {
                 validator_address,
                 self.teritorid_endpoint.clone(),
                 receiver,
+                alert_sender,
+                self.statuses.clone(),
             );
             runtime.spawn(checker.run());
             self.slashes_checker = Some(sender);
@@ -283,22 +533,68 @@ impl CheckManager {
         }
     }
 
-    async fn next(&mut self) -> Result<&mut Self> {
+    /// reports the number of messages the checkers have not processed yet.
+    fn observe_queue_depths(&self) {
+        let depths = [
+            (
+                AlertKind::IsSyncing,
+                self.is_syncing_checker.as_ref().map(Sender::len),
+            ),
+            (
+                AlertKind::NewProposal,
+                self.new_proposal_checker.as_ref().map(Sender::len),
+            ),
+            (
+                AlertKind::MissedBlock,
+                self.missed_block_checker.as_ref().map(Sender::len),
+            ),
+            (
+                AlertKind::ValidatorStatus,
+                self.validator_status_checker.as_ref().map(Sender::len),
+            ),
+            (
+                AlertKind::Slashes,
+                self.slashes_checker.as_ref().map(Sender::len),
+            ),
+        ];
+        for (kind, depth) in depths {
+            if let Some(depth) = depth {
+                metrics::CHECKER_QUEUE_DEPTH
+                    .with_label_values(&[self.teritorid_endpoint.as_str(), kind.as_str()])
+                    .set(depth as i64);
+            }
+        }
+    }
+
+    async fn next(&mut self) -> std::result::Result<&mut Self, CheckError> {
+        let attributes = vec![KeyValue::new("endpoint", self.teritorid_endpoint.clone())];
+        metrics::trace::in_result_span("CheckManager::next", attributes, self.check()).await
+    }
+
+    /// hands the checks over to the checkers, and catches up with the blocks since the last tick.
+    async fn check(&mut self) -> std::result::Result<&mut Self, CheckError> {
+        self.observe_queue_depths();
         if let Some(sender) = self.is_syncing_checker.as_mut() {
             sender
                 .try_send(IsSyncingMessage::Check)
-                .map_err(|err| anyhow!("{}", err))?;
+                .map_err(|cause| CheckError::Dispatch {
+                    checker: "is_syncing",
+                    cause,
+                })?;
         }
         if let Some(sender) = self.validator_status_checker.as_mut() {
             sender
                 .try_send(ValidatorStatusMessage::Check)
-                .map_err(|err| anyhow!("{}", err))?;
+                .map_err(|cause| CheckError::Dispatch {
+                    checker: "validator_status",
+                    cause,
+                })?;
         }
-        let latest_block_response = teritoricli::get_client(&self.teritorid_endpoint)
-            .lock()
+        let latest_block_response = teritoricli::lock_client(&self.teritorid_endpoint)
             .await
             .fetch_latest_block()
-            .await?;
+            .await
+            .map_err(|cause| CheckError::Unreachable { cause })?;
         let latest_height = latest_block_response
             .block
             .as_ref()
@@ -307,20 +603,29 @@ impl CheckManager {
             .as_ref()
             .unwrap()
             .height;
+        metrics::LATEST_BLOCK_HEIGHT
+            .with_label_values(&[self.teritorid_endpoint.as_str()])
+            .set(latest_height);
         let from_height = match self.latest_height {
             Some(last_checked_height) => last_checked_height + 1,
             None => latest_height,
         };
+        // the number of blocks to catch up with.
+        metrics::trace::set_attributes(vec![
+            KeyValue::new("from_height", from_height),
+            KeyValue::new("latest_height", latest_height),
+            KeyValue::new("blocks", (latest_height - from_height + 1).max(0)),
+        ]);
         if from_height <= latest_height {
             for height in from_height..=latest_height {
                 let block_message: BlockMessage = if height == latest_height {
                     latest_block_response.clone().into()
                 } else {
-                    let block_response = teritoricli::get_client(&self.teritorid_endpoint)
-                        .lock()
+                    let block_response = teritoricli::lock_client(&self.teritorid_endpoint)
                         .await
                         .fetch_block_by_height(height)
-                        .await?;
+                        .await
+                        .map_err(|cause| CheckError::Unreachable { cause })?;
                     block_response.into()
                 };
                 if let Some(sender) = self.new_proposal_checker.as_mut() {
@@ -330,7 +635,10 @@ impl CheckManager {
                     );
                     sender
                         .try_send(block_message.clone().into())
-                        .map_err(|err| anyhow!("{}", err))?;
+                        .map_err(|cause| CheckError::Dispatch {
+                            checker: "new_proposal",
+                            cause,
+                        })?;
                 }
                 if let Some(sender) = self.missed_block_checker.as_mut() {
                     info!(
@@ -340,7 +648,10 @@ impl CheckManager {
                     );
                     sender
                         .try_send(block_message.clone().into())
-                        .map_err(|err| anyhow!("{}", err))?;
+                        .map_err(|cause| CheckError::Dispatch {
+                            checker: "missed_block",
+                            cause,
+                        })?;
                 }
             }
             if let Some(sender) = self.slashes_checker.as_mut() {
@@ -349,10 +660,17 @@ impl CheckManager {
                         starting_height: from_height as u64,
                         ending_height: latest_height as u64,
                     }))
-                    .map_err(|err| anyhow!("{}", err))?;
+                    .map_err(|cause| CheckError::Dispatch {
+                        checker: "slashes",
+                        cause,
+                    })?;
             }
             self.latest_height = Some(latest_height)
         }
+        if let Some(latest_height) = self.latest_height {
+            self.statuses
+                .set_latest_height(self.teritorid_endpoint.as_str(), latest_height);
+        }
         Ok(self)
     }
 }
@@ -9,12 +9,24 @@ use std::process;
 use std::sync::Arc;
 use structopt::StructOpt;
 use teritoricli::TeritoridClient;
+use teritorimon::alerts::AlertsCommand;
+use teritorimon::history::HistoryCommand;
+use teritorimon::silence::SilenceCommand;
 
 #[derive(Debug, StructOpt)]
 #[structopt(about = "teritorimon running options")]
 struct Opts {
     #[structopt(short = "c", long, help = "Path to Config")]
     config: Option<PathBuf>,
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    Silence(SilenceCommand),
+    Alerts(AlertsCommand),
+    History(HistoryCommand),
 }
 
 fn main() {
@@ -35,6 +47,20 @@ fn main() {
         return;
     }
 
+    // run the subcommand instead of monitoring if any.
+    if let Some(command) = options.command {
+        let result = match command {
+            Command::Silence(command) => command.run(&config),
+            Command::Alerts(command) => command.run(&config),
+            Command::History(command) => command.run(&config),
+        };
+        if let Err(err) = result {
+            eprintln!("{:#}", err);
+            process::exit(1);
+        }
+        return;
+    }
+
     config::set_app_config(Arc::new(config));
 
     let config = config::app_config();
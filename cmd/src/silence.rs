@@ -0,0 +1,115 @@
+use crate::Result;
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Utc};
+use config::configs::ApplicationConfig;
+use notifier::silence::{AlertMatcher, SilenceFile};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(about = "Manage ad-hoc silences")]
+pub enum SilenceCommand {
+    #[structopt(about = "Silence matching alerts for a while")]
+    Add {
+        #[structopt(
+            long = "kind",
+            help = "Alert kind to silence (e.g. is_syncing), can be repeated"
+        )]
+        kinds: Vec<String>,
+        #[structopt(
+            long,
+            help = "Teritori daemon grpc endpoint (e.g. http://127.0.0.1:9090)"
+        )]
+        endpoint: Option<String>,
+        #[structopt(long, help = "Validator address")]
+        validator: Option<String>,
+        #[structopt(long, help = "How long the silence lasts (e.g. 2h)")]
+        duration: String,
+        #[structopt(long, help = "When the silence starts in RFC 3339 (default: now)")]
+        starts_at: Option<String>,
+        #[structopt(long)]
+        comment: Option<String>,
+    },
+    #[structopt(about = "List silences")]
+    List {
+        #[structopt(long, help = "Include expired silences")]
+        all: bool,
+    },
+    #[structopt(about = "Expire a silence right now")]
+    Expire { id: u64 },
+}
+
+impl SilenceCommand {
+    pub fn run(self, app_config: &ApplicationConfig) -> Result<()> {
+        let mut file = SilenceFile::load(app_config.alerting.silences_file.as_str())?;
+        match self {
+            Self::Add {
+                kinds,
+                endpoint,
+                validator,
+                duration,
+                starts_at,
+                comment,
+            } => {
+                let mut matcher = AlertMatcher {
+                    kinds: Vec::new(),
+                    endpoint,
+                    validator,
+                };
+                for kind in kinds {
+                    matcher.kinds.push(kind.parse()?);
+                }
+                if matcher.is_empty() {
+                    return Err(anyhow!(
+                        "at least one of --kind, --endpoint or --validator is required..."
+                    ));
+                }
+                let duration = duration_str::parse(duration.as_str())
+                    .with_context(|| format!("illegal duration: {}", duration))?;
+                let starts_at = match starts_at {
+                    Some(starts_at) => DateTime::parse_from_rfc3339(starts_at.as_str())
+                        .with_context(|| format!("illegal starts_at: {}", starts_at))?
+                        .with_timezone(&Utc),
+                    None => Utc::now(),
+                };
+                let ends_at = starts_at + chrono::Duration::from_std(duration)?;
+                let id = file.add(matcher, starts_at, ends_at, comment)?.id;
+                file.save()?;
+                println!(
+                    "silence {} has been added until {}",
+                    id,
+                    ends_at.to_rfc3339()
+                );
+            }
+            Self::List { all } => {
+                let now = Utc::now();
+                for silence in file.silences() {
+                    if !all && silence.is_expired(now) {
+                        continue;
+                    }
+                    let kinds: Vec<&str> =
+                        silence.matcher.kinds.iter().map(|k| k.as_str()).collect();
+                    println!(
+                        "#{}\t{} - {}\tkinds: {}\tendpoint: {}\tvalidator: {}\t{}",
+                        silence.id,
+                        silence.starts_at.to_rfc3339(),
+                        silence.ends_at.to_rfc3339(),
+                        if kinds.is_empty() {
+                            "*".to_owned()
+                        } else {
+                            kinds.join(",")
+                        },
+                        silence.matcher.endpoint.as_deref().unwrap_or("*"),
+                        silence.matcher.validator.as_deref().unwrap_or("*"),
+                        silence.comment.as_deref().unwrap_or_default(),
+                    );
+                }
+            }
+            Self::Expire { id } => {
+                file.expire(id)?;
+                file.save()?;
+                println!("silence {} has been expired", id);
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,12 +1,39 @@
 use crate::toml::*;
 use crate::{MissedBlockThreshold, Result};
 use anyhow::{anyhow, Context};
+use std::collections::BTreeMap;
 use std::env;
+use std::fmt;
+use std::net::SocketAddr;
 use std::path::Path;
 use std::str::FromStr;
 use std::time::Duration;
 
 const DEFAULT_INTERVAL: &'static str = "10s";
+const SEVERITIES: [&str; 4] = ["info", "warning", "error", "critical"];
+const ALERT_KINDS: [&str; 8] = [
+    "is_syncing",
+    "new_proposal",
+    "missed_block",
+    "validator_status",
+    "slashes",
+    "check_failure",
+    "unreachable",
+    "digest",
+];
+const DEFAULT_REPEAT_INTERVAL: &'static str = "4h";
+const DEFAULT_RATE_LIMIT_PERIOD: &'static str = "1m";
+const DEFAULT_SILENCES_FILE: &'static str = "silences.json";
+const DEFAULT_HOOK_CONCURRENCY: usize = 2;
+const DEFAULT_HOOK_AFTER: &'static str = "0s";
+const DEFAULT_HOOK_COOLDOWN: &'static str = "30m";
+const DEFAULT_HOOK_TIMEOUT: &'static str = "30s";
+const DEFAULT_API_LISTEN: &'static str = "127.0.0.1:9300";
+const DEFAULT_METRICS_LISTEN: &'static str = "127.0.0.1:9301";
+const DEFAULT_HISTORY_PATH: &'static str = "history.sqlite";
+const DEFAULT_TRACING_ENDPOINT: &'static str = "http://127.0.0.1:4317";
+const DEFAULT_TRACING_SERVICE_NAME: &'static str = "teritorimon";
+const DEFAULT_TRACING_TIMEOUT: &'static str = "10s";
 
 pub trait FromEnv: Sized {
     fn from_env() -> Result<Self>;
@@ -21,6 +48,27 @@ pub struct ApplicationConfig {
     pub interval: String,
     pub checkers: Vec<CheckerConfig>,
     pub logger: LoggerConfig,
+    pub notifiers: Vec<NotifierConfig>,
+    pub alerting: AlertingConfig,
+    pub silences: Vec<SilenceConfig>,
+    pub receivers: Vec<ReceiverConfig>,
+    pub route: Option<RouteConfig>,
+    pub inhibit_rules: Vec<InhibitRuleConfig>,
+    pub escalations: Vec<EscalationConfig>,
+    pub hooks: Vec<HookConfig>,
+    /// the local http api is disabled if not set
+    pub api: Option<ApiConfig>,
+    /// the prometheus exporter is disabled if not set
+    pub metrics: Option<MetricsConfig>,
+    pub digests: Vec<DigestConfig>,
+    /// alerts are not stored if not set
+    pub history: Option<HistoryConfig>,
+    /// spans are not exported if not set
+    pub tracing: Option<TracingConfig>,
+    /// handlebars templates of alert messages by alert kind
+    pub templates: BTreeMap<String, String>,
+    /// values available as `{{vars.<name>}}` in templates (e.g. explorer_url)
+    pub template_vars: BTreeMap<String, String>,
 }
 
 impl FromEnv for ApplicationConfig {
@@ -29,6 +77,21 @@ impl FromEnv for ApplicationConfig {
             interval: DEFAULT_INTERVAL.to_owned(),
             checkers: Vec::new(),
             logger: LoggerConfig::from_env()?,
+            notifiers: Vec::new(),
+            alerting: AlertingConfig::from_env()?,
+            silences: Vec::new(),
+            receivers: Vec::new(),
+            route: None,
+            inhibit_rules: Vec::new(),
+            escalations: Vec::new(),
+            hooks: Vec::new(),
+            api: None,
+            metrics: None,
+            digests: Vec::new(),
+            history: None,
+            tracing: None,
+            templates: BTreeMap::new(),
+            template_vars: BTreeMap::new(),
         })
     }
 }
@@ -41,6 +104,64 @@ impl SelfValidation for ApplicationConfig {
             let _ok = c.validate()?;
         }
         let _ok = self.logger.validate()?;
+        for n in &self.notifiers {
+            let _ok = n.validate()?;
+        }
+        let _ok = self.alerting.validate()?;
+        for s in &self.silences {
+            let _ok = s.validate()?;
+        }
+        for (i, r) in self.receivers.iter().enumerate() {
+            let _ok = r.validate()?;
+            if self.receivers[..i].iter().any(|other| other.name == r.name) {
+                return Err(anyhow!("receiver name is duplicated: {}", r.name));
+            }
+        }
+        if let Some(route) = self.route.as_ref() {
+            let _ok = route.validate()?;
+            for receiver in route.receivers() {
+                if !self.receivers.iter().any(|r| r.name == receiver) {
+                    return Err(anyhow!("receiver is not defined: {}", receiver));
+                }
+            }
+        }
+        for r in &self.inhibit_rules {
+            let _ok = r.validate()?;
+        }
+        for e in &self.escalations {
+            let _ok = e.validate()?;
+            for step in &e.steps {
+                if !self.receivers.iter().any(|r| r.name == step.receiver) {
+                    return Err(anyhow!("receiver is not defined: {}", step.receiver));
+                }
+            }
+        }
+        for h in &self.hooks {
+            let _ok = h.validate()?;
+        }
+        if let Some(api) = self.api.as_ref() {
+            let _ok = api.validate()?;
+        }
+        if let Some(metrics) = self.metrics.as_ref() {
+            let _ok = metrics.validate()?;
+        }
+        if let Some(history) = self.history.as_ref() {
+            let _ok = history.validate()?;
+        }
+        if let Some(tracing) = self.tracing.as_ref() {
+            let _ok = tracing.validate()?;
+        }
+        for d in &self.digests {
+            let _ok = d.validate()?;
+            for receiver in &d.receivers {
+                if !self.receivers.iter().any(|r| &r.name == receiver) {
+                    return Err(anyhow!("receiver is not defined: {}", receiver));
+                }
+            }
+        }
+        for kind in self.templates.keys() {
+            validate_alert_kind(kind)?;
+        }
         Ok(())
     }
 }
@@ -62,10 +183,84 @@ impl ApplicationConfig {
             Some(logger) => logger.try_into()?,
             None => LoggerConfig::from_env()?,
         };
+        let mut notifiers = Vec::new();
+        for notifier in app_toml.notifiers.unwrap_or_default() {
+            let notifier = notifier.try_into()?;
+            notifiers.push(notifier);
+        }
+        let alerting = match app_toml.alerting {
+            Some(alerting) => alerting.try_into()?,
+            None => AlertingConfig::from_env()?,
+        };
+        let mut silences = Vec::new();
+        for silence in app_toml.silences.unwrap_or_default() {
+            let silence = silence.try_into()?;
+            silences.push(silence);
+        }
+        let mut receivers = Vec::new();
+        for receiver in app_toml.receivers.unwrap_or_default() {
+            let receiver = receiver.try_into()?;
+            receivers.push(receiver);
+        }
+        let route = match app_toml.route {
+            Some(route) => Some(route.try_into()?),
+            None => None,
+        };
+        let mut inhibit_rules = Vec::new();
+        for inhibit_rule in app_toml.inhibit_rules.unwrap_or_default() {
+            let inhibit_rule = inhibit_rule.try_into()?;
+            inhibit_rules.push(inhibit_rule);
+        }
+        let mut escalations = Vec::new();
+        for escalation in app_toml.escalations.unwrap_or_default() {
+            let escalation = escalation.try_into()?;
+            escalations.push(escalation);
+        }
+        let mut hooks = Vec::new();
+        for hook in app_toml.hooks.unwrap_or_default() {
+            let hook = hook.try_into()?;
+            hooks.push(hook);
+        }
+        let api = match app_toml.api {
+            Some(api) => Some(api.try_into()?),
+            None => None,
+        };
+        let metrics = match app_toml.metrics {
+            Some(metrics) => Some(metrics.try_into()?),
+            None => None,
+        };
+        let mut digests = Vec::new();
+        for digest in app_toml.digests.unwrap_or_default() {
+            let digest = digest.try_into()?;
+            digests.push(digest);
+        }
+        let history = match app_toml.history {
+            Some(history) => Some(history.try_into()?),
+            None => None,
+        };
+        let tracing = match app_toml.tracing {
+            Some(tracing) => Some(tracing.try_into()?),
+            None => None,
+        };
         Ok(Self {
             interval,
             checkers,
             logger,
+            notifiers,
+            alerting,
+            silences,
+            receivers,
+            route,
+            inhibit_rules,
+            escalations,
+            hooks,
+            api,
+            metrics,
+            digests,
+            history,
+            tracing,
+            templates: app_toml.templates.unwrap_or_default(),
+            template_vars: app_toml.template_vars.unwrap_or_default(),
         })
     }
 
@@ -87,6 +282,9 @@ pub struct CheckerConfig {
     pub missed_block_threshold: Option<MissedBlockThreshold>,
     pub validator_status: bool,
     pub slashes: bool,
+    pub notifiers: Vec<NotifierConfig>,
+    /// custom labels attached to every alert of this checker, to route alerts with.
+    pub labels: BTreeMap<String, String>,
 }
 
 impl CheckerConfig {
@@ -117,6 +315,9 @@ impl SelfValidation for CheckerConfig {
                 return Err(anyhow!("validator_address is missing..."));
             }
         }
+        for n in &self.notifiers {
+            let _ok = n.validate()?;
+        }
         Ok(())
     }
 }
@@ -135,6 +336,8 @@ impl FromEnv for CheckerConfig {
             missed_block_threshold: None,
             validator_status: false,
             slashes: false,
+            notifiers: Vec::new(),
+            labels: BTreeMap::new(),
         })
     }
 }
@@ -177,6 +380,14 @@ impl TryFrom<CheckerToml> for CheckerConfig {
         if let Some(slashes) = toml.slashes {
             result.slashes = slashes;
         }
+        if let Some(notifiers) = toml.notifiers {
+            for notifier in notifiers {
+                result.notifiers.push(notifier.try_into()?);
+            }
+        }
+        if let Some(labels) = toml.labels {
+            result.labels = labels;
+        }
         Ok(result)
     }
 }
@@ -248,6 +459,1438 @@ impl TryFrom<LoggerToml> for LoggerConfig {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlertingConfig {
+    /// how often to notify again while a condition keeps firing.
+    /// identical one-shot alerts are not notified again within this interval either.
+    pub repeat_interval: String,
+    /// `repeat_interval` per alert kind (e.g. `is_syncing = '30m'`)
+    pub repeat_intervals: BTreeMap<String, String>,
+    /// the max number of notifications per `rate_limit_period`. (no limit if not set)
+    pub rate_limit: Option<u32>,
+    pub rate_limit_period: String,
+    /// where ad-hoc silences created by `teritorimon silence` are stored
+    pub silences_file: String,
+    /// the max number of remediation hooks running at once
+    pub hook_concurrency: usize,
+}
+
+impl AlertingConfig {
+    pub fn get_repeat_interval(&self, kind: &str) -> Duration {
+        let repeat_interval = self
+            .repeat_intervals
+            .get(kind)
+            .unwrap_or(&self.repeat_interval);
+        duration_str::parse(repeat_interval.as_str())
+            .expect("illegal repeat_interval config value...")
+    }
+
+    pub fn get_rate_limit_period(&self) -> Duration {
+        duration_str::parse(self.rate_limit_period.as_str())
+            .expect("illegal rate_limit_period config value...")
+    }
+}
+
+impl FromEnv for AlertingConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            repeat_interval: DEFAULT_REPEAT_INTERVAL.to_owned(),
+            repeat_intervals: BTreeMap::new(),
+            rate_limit: None,
+            rate_limit_period: DEFAULT_RATE_LIMIT_PERIOD.to_owned(),
+            silences_file: DEFAULT_SILENCES_FILE.to_owned(),
+            hook_concurrency: DEFAULT_HOOK_CONCURRENCY,
+        })
+    }
+}
+
+impl SelfValidation for AlertingConfig {
+    fn validate(&self) -> Result<()> {
+        let _repeat_interval = duration_str::parse(self.repeat_interval.as_str())
+            .with_context(|| format!("illegal repeat_interval: {}", self.repeat_interval))?;
+        for (kind, repeat_interval) in &self.repeat_intervals {
+            validate_alert_kind(kind)?;
+            let _repeat_interval = duration_str::parse(repeat_interval.as_str())
+                .with_context(|| format!("illegal repeat_interval: {}", repeat_interval))?;
+        }
+        if self.rate_limit == Some(0) {
+            return Err(anyhow!("rate_limit must be greater than 0..."));
+        }
+        let _rate_limit_period = duration_str::parse(self.rate_limit_period.as_str())
+            .with_context(|| format!("illegal rate_limit_period: {}", self.rate_limit_period))?;
+        if self.hook_concurrency == 0 {
+            return Err(anyhow!("hook_concurrency must be greater than 0..."));
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<AlertingToml> for AlertingConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: AlertingToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(repeat_interval) = toml.repeat_interval {
+            result.repeat_interval = repeat_interval;
+        }
+        if let Some(repeat_intervals) = toml.repeat_intervals {
+            result.repeat_intervals = repeat_intervals;
+        }
+        if let Some(rate_limit) = toml.rate_limit {
+            result.rate_limit = Some(rate_limit);
+        }
+        if let Some(rate_limit_period) = toml.rate_limit_period {
+            result.rate_limit_period = rate_limit_period;
+        }
+        if let Some(silences_file) = toml.silences_file {
+            result.silences_file = silences_file;
+        }
+        if let Some(hook_concurrency) = toml.hook_concurrency {
+            result.hook_concurrency = hook_concurrency;
+        }
+        Ok(result)
+    }
+}
+
+/// the local http api to list and acknowledge active alerts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiConfig {
+    pub listen: String,
+    /// the base url of the links embedded in messages (e.g. behind a reverse proxy)
+    pub external_url: Option<String>,
+    /// whether to serve the web dashboard on `/dashboard`
+    pub dashboard: bool,
+}
+
+impl ApiConfig {
+    pub fn get_listen(&self) -> SocketAddr {
+        self.listen
+            .parse()
+            .expect("illegal api listen config value...")
+    }
+
+    pub fn get_external_url(&self) -> String {
+        match self.external_url.as_deref() {
+            Some(external_url) => external_url.trim_end_matches('/').to_owned(),
+            None => format!("http://{}", self.listen),
+        }
+    }
+}
+
+impl FromEnv for ApiConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            listen: DEFAULT_API_LISTEN.to_owned(),
+            external_url: None,
+            dashboard: true,
+        })
+    }
+}
+
+impl SelfValidation for ApiConfig {
+    fn validate(&self) -> Result<()> {
+        let _listen = SocketAddr::from_str(self.listen.as_str())
+            .with_context(|| format!("illegal api listen address: {}", self.listen))?;
+        if let Some(external_url) = self.external_url.as_deref() {
+            if !external_url.starts_with("http://") && !external_url.starts_with("https://") {
+                return Err(anyhow!("illegal api external_url: {}", external_url));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<ApiToml> for ApiConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: ApiToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(listen) = toml.listen {
+            result.listen = listen;
+        }
+        if let Some(external_url) = toml.external_url {
+            result.external_url = Some(external_url);
+        }
+        if let Some(dashboard) = toml.dashboard {
+            result.dashboard = dashboard;
+        }
+        Ok(result)
+    }
+}
+
+/// the http server to expose prometheus metrics on `/metrics`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricsConfig {
+    pub listen: String,
+}
+
+impl MetricsConfig {
+    pub fn get_listen(&self) -> SocketAddr {
+        self.listen
+            .parse()
+            .expect("illegal metrics listen config value...")
+    }
+}
+
+impl FromEnv for MetricsConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            listen: DEFAULT_METRICS_LISTEN.to_owned(),
+        })
+    }
+}
+
+impl SelfValidation for MetricsConfig {
+    fn validate(&self) -> Result<()> {
+        let _listen = SocketAddr::from_str(self.listen.as_str())
+            .with_context(|| format!("illegal metrics listen address: {}", self.listen))?;
+        Ok(())
+    }
+}
+
+impl TryFrom<MetricsToml> for MetricsConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: MetricsToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(listen) = toml.listen {
+            result.listen = listen;
+        }
+        Ok(result)
+    }
+}
+
+/// the sqlite database to store the alerts and their state transitions in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryConfig {
+    pub path: String,
+}
+
+impl FromEnv for HistoryConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            path: DEFAULT_HISTORY_PATH.to_owned(),
+        })
+    }
+}
+
+impl SelfValidation for HistoryConfig {
+    fn validate(&self) -> Result<()> {
+        if self.path.is_empty() {
+            return Err(anyhow!("history path is missing..."));
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<HistoryToml> for HistoryConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: HistoryToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(path) = toml.path {
+            result.path = path;
+        }
+        Ok(result)
+    }
+}
+
+/// the opentelemetry collector to export spans to via otlp (grpc).
+#[derive(Clone, Debug, PartialEq)]
+pub struct TracingConfig {
+    pub endpoint: String,
+    /// `service.name` of the exported spans
+    pub service_name: String,
+    /// how long to wait for the collector to receive a batch of spans
+    pub timeout: String,
+}
+
+impl TracingConfig {
+    pub fn get_timeout(&self) -> Duration {
+        duration_str::parse(self.timeout.as_str()).expect("illegal tracing timeout config value...")
+    }
+}
+
+impl FromEnv for TracingConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            endpoint: DEFAULT_TRACING_ENDPOINT.to_owned(),
+            service_name: DEFAULT_TRACING_SERVICE_NAME.to_owned(),
+            timeout: DEFAULT_TRACING_TIMEOUT.to_owned(),
+        })
+    }
+}
+
+impl SelfValidation for TracingConfig {
+    fn validate(&self) -> Result<()> {
+        if !self.endpoint.starts_with("http://") && !self.endpoint.starts_with("https://") {
+            return Err(anyhow!("illegal tracing endpoint: {}", self.endpoint));
+        }
+        if self.service_name.is_empty() {
+            return Err(anyhow!("tracing service_name is missing..."));
+        }
+        let _timeout = duration_str::parse(self.timeout.as_str())
+            .with_context(|| format!("illegal tracing timeout: {}", self.timeout))?;
+        Ok(())
+    }
+}
+
+impl TryFrom<TracingToml> for TracingConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: TracingToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(endpoint) = toml.endpoint {
+            result.endpoint = endpoint;
+        }
+        if let Some(service_name) = toml.service_name {
+            result.service_name = service_name;
+        }
+        if let Some(timeout) = toml.timeout {
+            result.timeout = timeout;
+        }
+        Ok(result)
+    }
+}
+
+/// a scheduled summary report per validator (e.g. daily or weekly).
+#[derive(Clone, Debug, PartialEq)]
+pub struct DigestConfig {
+    pub name: String,
+    /// cron expression of when to send the report. (`sec min hour day_of_month month day_of_week`)
+    pub schedule: String,
+    pub timezone: String,
+    /// receivers to send the report to. (the global notifiers if empty)
+    pub receivers: Vec<String>,
+}
+
+impl DigestConfig {
+    pub fn get_schedule(&self) -> cron::Schedule {
+        cron::Schedule::from_str(self.schedule.as_str()).expect("illegal schedule config value...")
+    }
+
+    pub fn get_timezone(&self) -> chrono_tz::Tz {
+        self.timezone
+            .parse()
+            .expect("illegal timezone config value...")
+    }
+}
+
+impl FromEnv for DigestConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            name: "digest".to_owned(),
+            schedule: "".to_owned(),
+            timezone: "UTC".to_owned(),
+            receivers: Vec::new(),
+        })
+    }
+}
+
+impl SelfValidation for DigestConfig {
+    fn validate(&self) -> Result<()> {
+        if self.schedule.is_empty() {
+            return Err(anyhow!("digest schedule is missing..."));
+        }
+        let _schedule = cron::Schedule::from_str(self.schedule.as_str())
+            .with_context(|| format!("illegal digest schedule: {}", self.schedule))?;
+        let _timezone: chrono_tz::Tz = self
+            .timezone
+            .parse()
+            .map_err(|_| anyhow!("illegal digest timezone: {}", self.timezone))?;
+        Ok(())
+    }
+}
+
+impl TryFrom<DigestToml> for DigestConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: DigestToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(name) = toml.name {
+            result.name = name;
+        }
+        if let Some(schedule) = toml.schedule {
+            result.schedule = schedule;
+        }
+        if let Some(timezone) = toml.timezone {
+            result.timezone = timezone;
+        }
+        if let Some(receivers) = toml.receivers {
+            result.receivers = receivers;
+        }
+        Ok(result)
+    }
+}
+
+/// a recurring window (e.g. weekly maintenance) in which matching alerts are not delivered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SilenceConfig {
+    /// alert kinds to silence. (all kinds if empty)
+    pub kinds: Vec<String>,
+    pub endpoint: Option<String>,
+    pub validator: Option<String>,
+    /// cron expression of when the window starts. (`sec min hour day_of_month month day_of_week`)
+    pub schedule: String,
+    /// how long the window lasts
+    pub duration: String,
+    pub timezone: String,
+    pub comment: Option<String>,
+}
+
+impl SilenceConfig {
+    pub fn get_schedule(&self) -> cron::Schedule {
+        cron::Schedule::from_str(self.schedule.as_str()).expect("illegal schedule config value...")
+    }
+
+    pub fn get_duration(&self) -> Duration {
+        duration_str::parse(self.duration.as_str()).expect("illegal duration config value...")
+    }
+
+    pub fn get_timezone(&self) -> chrono_tz::Tz {
+        self.timezone
+            .parse()
+            .expect("illegal timezone config value...")
+    }
+}
+
+impl FromEnv for SilenceConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            kinds: Vec::new(),
+            endpoint: None,
+            validator: None,
+            schedule: "".to_owned(),
+            duration: "".to_owned(),
+            timezone: "UTC".to_owned(),
+            comment: None,
+        })
+    }
+}
+
+impl SelfValidation for SilenceConfig {
+    fn validate(&self) -> Result<()> {
+        for kind in &self.kinds {
+            validate_alert_kind(kind)?;
+        }
+        if self.schedule.is_empty() {
+            return Err(anyhow!("silence schedule is missing..."));
+        }
+        let _schedule = cron::Schedule::from_str(self.schedule.as_str())
+            .with_context(|| format!("illegal silence schedule: {}", self.schedule))?;
+        let _duration = duration_str::parse(self.duration.as_str())
+            .with_context(|| format!("illegal silence duration: {}", self.duration))?;
+        let _timezone: chrono_tz::Tz = self
+            .timezone
+            .parse()
+            .map_err(|_| anyhow!("illegal silence timezone: {}", self.timezone))?;
+        Ok(())
+    }
+}
+
+impl TryFrom<SilenceToml> for SilenceConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: SilenceToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(kinds) = toml.kinds {
+            result.kinds = kinds;
+        }
+        if let Some(endpoint) = toml.endpoint {
+            result.endpoint = Some(endpoint);
+        }
+        if let Some(validator) = toml.validator {
+            result.validator = Some(validator);
+        }
+        if let Some(schedule) = toml.schedule {
+            result.schedule = schedule;
+        }
+        if let Some(duration) = toml.duration {
+            result.duration = duration;
+        }
+        if let Some(timezone) = toml.timezone {
+            result.timezone = timezone;
+        }
+        if let Some(comment) = toml.comment {
+            result.comment = Some(comment);
+        }
+        Ok(result)
+    }
+}
+
+/// a named set of notifiers which routes deliver alerts to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReceiverConfig {
+    pub name: String,
+    pub notifiers: Vec<NotifierConfig>,
+    /// templates of alert messages by alert kind, which override the global ones
+    pub templates: BTreeMap<String, String>,
+}
+
+impl FromEnv for ReceiverConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            name: "".to_owned(),
+            notifiers: Vec::new(),
+            templates: BTreeMap::new(),
+        })
+    }
+}
+
+impl SelfValidation for ReceiverConfig {
+    fn validate(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("receiver name is missing..."));
+        }
+        for n in &self.notifiers {
+            let _ok = n.validate()?;
+        }
+        for kind in self.templates.keys() {
+            validate_alert_kind(kind)?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<ReceiverToml> for ReceiverConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: ReceiverToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(name) = toml.name {
+            result.name = name;
+        }
+        if let Some(notifiers) = toml.notifiers {
+            for notifier in notifiers {
+                result.notifiers.push(notifier.try_into()?);
+            }
+        }
+        if let Some(templates) = toml.templates {
+            result.templates = templates;
+        }
+        Ok(result)
+    }
+}
+
+/// a node of the routing tree. see the alertmanager `route` for the semantics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteConfig {
+    /// inherited from the parent route if not set
+    pub receiver: Option<String>,
+    /// labels which alerts must have. (e.g. `kind = 'new_proposal'`)
+    pub matchers: BTreeMap<String, String>,
+    /// whether to keep matching the following sibling routes after this route matched
+    pub continue_matching: bool,
+    pub routes: Vec<RouteConfig>,
+}
+
+impl RouteConfig {
+    /// every receiver name referred in the tree.
+    pub fn receivers(&self) -> Vec<&str> {
+        let mut result: Vec<&str> = self.receiver.as_deref().into_iter().collect();
+        for route in &self.routes {
+            result.extend(route.receivers());
+        }
+        result
+    }
+}
+
+impl FromEnv for RouteConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            receiver: None,
+            matchers: BTreeMap::new(),
+            continue_matching: false,
+            routes: Vec::new(),
+        })
+    }
+}
+
+impl SelfValidation for RouteConfig {
+    fn validate(&self) -> Result<()> {
+        validate_label_matchers(&self.matchers)?;
+        for r in &self.routes {
+            let _ok = r.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<RouteToml> for RouteConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: RouteToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(receiver) = toml.receiver {
+            result.receiver = Some(receiver);
+        }
+        if let Some(matchers) = toml.matchers {
+            result.matchers = matchers;
+        }
+        if let Some(continue_matching) = toml.continue_matching {
+            result.continue_matching = continue_matching;
+        }
+        if let Some(routes) = toml.routes {
+            for route in routes {
+                result.routes.push(route.try_into()?);
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// mutes alerts matching `target_match` while an alert matching `source_match` is firing,
+/// if both have the same values of the `equal` labels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InhibitRuleConfig {
+    pub source_match: BTreeMap<String, String>,
+    pub target_match: BTreeMap<String, String>,
+    pub equal: Vec<String>,
+}
+
+impl FromEnv for InhibitRuleConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            source_match: BTreeMap::new(),
+            target_match: BTreeMap::new(),
+            equal: Vec::new(),
+        })
+    }
+}
+
+impl SelfValidation for InhibitRuleConfig {
+    fn validate(&self) -> Result<()> {
+        if self.source_match.is_empty() {
+            return Err(anyhow!("inhibit rule source_match is missing..."));
+        }
+        if self.target_match.is_empty() {
+            return Err(anyhow!("inhibit rule target_match is missing..."));
+        }
+        validate_label_matchers(&self.source_match)?;
+        validate_label_matchers(&self.target_match)?;
+        Ok(())
+    }
+}
+
+impl TryFrom<InhibitRuleToml> for InhibitRuleConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: InhibitRuleToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(source_match) = toml.source_match {
+            result.source_match = source_match;
+        }
+        if let Some(target_match) = toml.target_match {
+            result.target_match = target_match;
+        }
+        if let Some(equal) = toml.equal {
+            result.equal = equal;
+        }
+        Ok(result)
+    }
+}
+
+/// escalates alerts matching `matchers` to the next receivers step by step,
+/// until they are acknowledged or resolved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EscalationConfig {
+    /// labels which alerts must have. (e.g. `kind = 'validator_status'`)
+    pub matchers: BTreeMap<String, String>,
+    pub steps: Vec<EscalationStepConfig>,
+}
+
+impl FromEnv for EscalationConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            matchers: BTreeMap::new(),
+            steps: Vec::new(),
+        })
+    }
+}
+
+impl SelfValidation for EscalationConfig {
+    fn validate(&self) -> Result<()> {
+        validate_label_matchers(&self.matchers)?;
+        if self.steps.is_empty() {
+            return Err(anyhow!("escalation steps are missing..."));
+        }
+        for s in &self.steps {
+            let _ok = s.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<EscalationToml> for EscalationConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: EscalationToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(matchers) = toml.matchers {
+            result.matchers = matchers;
+        }
+        if let Some(steps) = toml.steps {
+            for step in steps {
+                result.steps.push(step.try_into()?);
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EscalationStepConfig {
+    /// how long after the alert started firing to escalate to the receiver
+    pub after: String,
+    pub receiver: String,
+}
+
+impl EscalationStepConfig {
+    pub fn get_after(&self) -> Duration {
+        duration_str::parse(self.after.as_str()).expect("illegal escalation after config value...")
+    }
+}
+
+impl FromEnv for EscalationStepConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            after: "".to_owned(),
+            receiver: "".to_owned(),
+        })
+    }
+}
+
+impl SelfValidation for EscalationStepConfig {
+    fn validate(&self) -> Result<()> {
+        let _after = duration_str::parse(self.after.as_str())
+            .with_context(|| format!("illegal escalation after: {}", self.after))?;
+        if self.receiver.is_empty() {
+            return Err(anyhow!("escalation receiver is missing..."));
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<EscalationStepToml> for EscalationStepConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: EscalationStepToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(after) = toml.after {
+            result.after = after;
+        }
+        if let Some(receiver) = toml.receiver {
+            result.receiver = receiver;
+        }
+        Ok(result)
+    }
+}
+
+/// runs a local executable (e.g. to restart a stuck sentry) while alerts matching `matchers` are firing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HookConfig {
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    /// labels which alerts must have. (e.g. `kind = 'is_syncing'`)
+    pub matchers: BTreeMap<String, String>,
+    /// how long the alert must have been firing before running the command
+    pub after: String,
+    /// how long to wait before running the command again while the alert keeps firing
+    pub cooldown: String,
+    /// the command is killed if it takes longer than this
+    pub timeout: String,
+}
+
+impl HookConfig {
+    pub fn get_after(&self) -> Duration {
+        duration_str::parse(self.after.as_str()).expect("illegal hook after config value...")
+    }
+
+    pub fn get_cooldown(&self) -> Duration {
+        duration_str::parse(self.cooldown.as_str()).expect("illegal hook cooldown config value...")
+    }
+
+    pub fn get_timeout(&self) -> Duration {
+        duration_str::parse(self.timeout.as_str()).expect("illegal hook timeout config value...")
+    }
+}
+
+impl FromEnv for HookConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            name: "".to_owned(),
+            command: "".to_owned(),
+            args: Vec::new(),
+            matchers: BTreeMap::new(),
+            after: DEFAULT_HOOK_AFTER.to_owned(),
+            cooldown: DEFAULT_HOOK_COOLDOWN.to_owned(),
+            timeout: DEFAULT_HOOK_TIMEOUT.to_owned(),
+        })
+    }
+}
+
+impl SelfValidation for HookConfig {
+    fn validate(&self) -> Result<()> {
+        if self.command.is_empty() {
+            return Err(anyhow!("hook command is missing..."));
+        }
+        validate_label_matchers(&self.matchers)?;
+        let _after = duration_str::parse(self.after.as_str())
+            .with_context(|| format!("illegal hook after: {}", self.after))?;
+        let _cooldown = duration_str::parse(self.cooldown.as_str())
+            .with_context(|| format!("illegal hook cooldown: {}", self.cooldown))?;
+        let timeout = duration_str::parse(self.timeout.as_str())
+            .with_context(|| format!("illegal hook timeout: {}", self.timeout))?;
+        if timeout.is_zero() {
+            return Err(anyhow!("hook timeout must be greater than 0..."));
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<HookToml> for HookConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: HookToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(command) = toml.command {
+            result.command = command;
+        }
+        result.name = toml.name.unwrap_or_else(|| result.command.clone());
+        if let Some(args) = toml.args {
+            result.args = args;
+        }
+        if let Some(matchers) = toml.matchers {
+            result.matchers = matchers;
+        }
+        if let Some(after) = toml.after {
+            result.after = after;
+        }
+        if let Some(cooldown) = toml.cooldown {
+            result.cooldown = cooldown;
+        }
+        if let Some(timeout) = toml.timeout {
+            result.timeout = timeout;
+        }
+        Ok(result)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum NotifierConfig {
+    Slack(SlackConfig),
+    Discord(DiscordConfig),
+    Telegram(TelegramConfig),
+    Pagerduty(PagerDutyConfig),
+    Opsgenie(OpsgenieConfig),
+    Webhook(WebhookConfig),
+    Email(EmailConfig),
+    Matrix(MatrixConfig),
+}
+
+impl SelfValidation for NotifierConfig {
+    fn validate(&self) -> Result<()> {
+        match self {
+            Self::Slack(config) => config.validate(),
+            Self::Discord(config) => config.validate(),
+            Self::Telegram(config) => config.validate(),
+            Self::Pagerduty(config) => config.validate(),
+            Self::Opsgenie(config) => config.validate(),
+            Self::Webhook(config) => config.validate(),
+            Self::Email(config) => config.validate(),
+            Self::Matrix(config) => config.validate(),
+        }
+    }
+}
+
+impl TryFrom<NotifierToml> for NotifierConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: NotifierToml) -> Result<Self> {
+        match toml {
+            NotifierToml::Slack(toml) => Ok(Self::Slack(toml.try_into()?)),
+            NotifierToml::Discord(toml) => Ok(Self::Discord(toml.try_into()?)),
+            NotifierToml::Telegram(toml) => Ok(Self::Telegram(toml.try_into()?)),
+            NotifierToml::Pagerduty(toml) => Ok(Self::Pagerduty(toml.try_into()?)),
+            NotifierToml::Opsgenie(toml) => Ok(Self::Opsgenie(toml.try_into()?)),
+            NotifierToml::Webhook(toml) => Ok(Self::Webhook(toml.try_into()?)),
+            NotifierToml::Email(toml) => Ok(Self::Email(toml.try_into()?)),
+            NotifierToml::Matrix(toml) => Ok(Self::Matrix(toml.try_into()?)),
+        }
+    }
+}
+
+/// printed in place of secrets (e.g. when the config is logged)
+const REDACTED: &str = "<redacted>";
+
+#[derive(Clone, PartialEq)]
+pub struct SlackConfig {
+    pub webhook_url: String,
+    pub channel: Option<String>,
+    pub username: Option<String>,
+    pub icon_emoji: Option<String>,
+}
+
+impl fmt::Debug for SlackConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlackConfig")
+            .field("webhook_url", &REDACTED)
+            .field("channel", &self.channel)
+            .field("username", &self.username)
+            .field("icon_emoji", &self.icon_emoji)
+            .finish()
+    }
+}
+
+impl FromEnv for SlackConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            webhook_url: "".to_owned(),
+            channel: None,
+            username: None,
+            icon_emoji: None,
+        })
+    }
+}
+
+impl SelfValidation for SlackConfig {
+    fn validate(&self) -> Result<()> {
+        if self.webhook_url.is_empty() {
+            return Err(anyhow!("slack webhook_url is missing..."));
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<SlackToml> for SlackConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: SlackToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(webhook_url) = toml.webhook_url {
+            result.webhook_url = webhook_url;
+        }
+        if let Some(channel) = toml.channel {
+            result.channel = Some(channel);
+        }
+        if let Some(username) = toml.username {
+            result.username = Some(username);
+        }
+        if let Some(icon_emoji) = toml.icon_emoji {
+            result.icon_emoji = Some(icon_emoji);
+        }
+        Ok(result)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct DiscordConfig {
+    pub webhook_url: String,
+    pub username: Option<String>,
+    pub avatar_url: Option<String>,
+    /// role ids to mention on critical alerts (e.g. jailing or slashes)
+    pub mention_roles: Vec<String>,
+    /// how many times to retry when discord responds with rate limit
+    pub max_retries: u32,
+}
+
+impl fmt::Debug for DiscordConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DiscordConfig")
+            .field("webhook_url", &REDACTED)
+            .field("username", &self.username)
+            .field("avatar_url", &self.avatar_url)
+            .field("mention_roles", &self.mention_roles)
+            .field("max_retries", &self.max_retries)
+            .finish()
+    }
+}
+
+impl FromEnv for DiscordConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            webhook_url: "".to_owned(),
+            username: None,
+            avatar_url: None,
+            mention_roles: Vec::new(),
+            max_retries: 3,
+        })
+    }
+}
+
+impl SelfValidation for DiscordConfig {
+    fn validate(&self) -> Result<()> {
+        if self.webhook_url.is_empty() {
+            return Err(anyhow!("discord webhook_url is missing..."));
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<DiscordToml> for DiscordConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: DiscordToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(webhook_url) = toml.webhook_url {
+            result.webhook_url = webhook_url;
+        }
+        if let Some(username) = toml.username {
+            result.username = Some(username);
+        }
+        if let Some(avatar_url) = toml.avatar_url {
+            result.avatar_url = Some(avatar_url);
+        }
+        if let Some(mention_roles) = toml.mention_roles {
+            result.mention_roles = mention_roles;
+        }
+        if let Some(max_retries) = toml.max_retries {
+            result.max_retries = max_retries;
+        }
+        Ok(result)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct TelegramConfig {
+    pub bot_token: String,
+    pub chat_id: String,
+    pub api_base_url: String,
+}
+
+impl fmt::Debug for TelegramConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TelegramConfig")
+            .field("bot_token", &REDACTED)
+            .field("chat_id", &self.chat_id)
+            .field("api_base_url", &self.api_base_url)
+            .finish()
+    }
+}
+
+impl FromEnv for TelegramConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            bot_token: "".to_owned(),
+            chat_id: "".to_owned(),
+            api_base_url: "https://api.telegram.org".to_owned(),
+        })
+    }
+}
+
+impl SelfValidation for TelegramConfig {
+    fn validate(&self) -> Result<()> {
+        if self.bot_token.is_empty() {
+            return Err(anyhow!("telegram bot_token is missing..."));
+        }
+        if self.chat_id.is_empty() {
+            return Err(anyhow!("telegram chat_id is missing..."));
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<TelegramToml> for TelegramConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: TelegramToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(bot_token) = toml.bot_token {
+            result.bot_token = bot_token;
+        }
+        if let Some(chat_id) = toml.chat_id {
+            result.chat_id = chat_id;
+        }
+        if let Some(api_base_url) = toml.api_base_url {
+            result.api_base_url = api_base_url;
+        }
+        Ok(result)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct PagerDutyConfig {
+    pub routing_key: String,
+    pub events_url: String,
+    /// alerts less severe than this are not sent. (new proposals should not page anyone...)
+    pub min_severity: String,
+}
+
+impl fmt::Debug for PagerDutyConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PagerDutyConfig")
+            .field("routing_key", &REDACTED)
+            .field("events_url", &self.events_url)
+            .field("min_severity", &self.min_severity)
+            .finish()
+    }
+}
+
+impl FromEnv for PagerDutyConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            routing_key: "".to_owned(),
+            events_url: "https://events.pagerduty.com/v2/enqueue".to_owned(),
+            min_severity: "error".to_owned(),
+        })
+    }
+}
+
+impl SelfValidation for PagerDutyConfig {
+    fn validate(&self) -> Result<()> {
+        if self.routing_key.is_empty() {
+            return Err(anyhow!("pagerduty routing_key is missing..."));
+        }
+        validate_severity(self.min_severity.as_str())
+    }
+}
+
+impl TryFrom<PagerDutyToml> for PagerDutyConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: PagerDutyToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(routing_key) = toml.routing_key {
+            result.routing_key = routing_key;
+        }
+        if let Some(events_url) = toml.events_url {
+            result.events_url = events_url;
+        }
+        if let Some(min_severity) = toml.min_severity {
+            result.min_severity = min_severity;
+        }
+        Ok(result)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct OpsgenieConfig {
+    pub api_key: String,
+    pub api_url: String,
+    /// the team to be the responder of alerts
+    pub team: Option<String>,
+    /// tags attached to every alert in addition to the checker name and the teritorid host
+    pub tags: Vec<String>,
+}
+
+impl fmt::Debug for OpsgenieConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpsgenieConfig")
+            .field("api_key", &REDACTED)
+            .field("api_url", &self.api_url)
+            .field("team", &self.team)
+            .field("tags", &self.tags)
+            .finish()
+    }
+}
+
+impl FromEnv for OpsgenieConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            api_key: "".to_owned(),
+            api_url: "https://api.opsgenie.com".to_owned(),
+            team: None,
+            tags: Vec::new(),
+        })
+    }
+}
+
+impl SelfValidation for OpsgenieConfig {
+    fn validate(&self) -> Result<()> {
+        if self.api_key.is_empty() {
+            return Err(anyhow!("opsgenie api_key is missing..."));
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<OpsgenieToml> for OpsgenieConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: OpsgenieToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(api_key) = toml.api_key {
+            result.api_key = api_key;
+        }
+        if let Some(api_url) = toml.api_url {
+            result.api_url = api_url;
+        }
+        if let Some(team) = toml.team {
+            result.team = Some(team);
+        }
+        if let Some(tags) = toml.tags {
+            result.tags = tags;
+        }
+        Ok(result)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct WebhookConfig {
+    pub url: String,
+    pub method: String,
+    pub headers: BTreeMap<String, String>,
+    /// handlebars template of the request body. the alert itself is sent as json if not set.
+    pub body_template: Option<String>,
+    /// the key to sign the request body with HMAC-SHA256
+    pub secret: Option<String>,
+    pub signature_header: String,
+}
+
+impl fmt::Debug for WebhookConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // header values may be credentials too (e.g. `Authorization`)
+        let headers: BTreeMap<&String, &str> =
+            self.headers.keys().map(|name| (name, REDACTED)).collect();
+        f.debug_struct("WebhookConfig")
+            .field("url", &self.url)
+            .field("method", &self.method)
+            .field("headers", &headers)
+            .field("body_template", &self.body_template)
+            .field("secret", &self.secret.as_ref().map(|_| REDACTED))
+            .field("signature_header", &self.signature_header)
+            .finish()
+    }
+}
+
+impl FromEnv for WebhookConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            url: "".to_owned(),
+            method: "POST".to_owned(),
+            headers: BTreeMap::new(),
+            body_template: None,
+            secret: None,
+            signature_header: "X-Teritorimon-Signature".to_owned(),
+        })
+    }
+}
+
+impl SelfValidation for WebhookConfig {
+    fn validate(&self) -> Result<()> {
+        if self.url.is_empty() {
+            return Err(anyhow!("webhook url is missing..."));
+        }
+        if !["POST", "PUT", "PATCH"].contains(&self.method.as_str()) {
+            return Err(anyhow!("illegal webhook method: {}", self.method));
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<WebhookToml> for WebhookConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: WebhookToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(url) = toml.url {
+            result.url = url;
+        }
+        if let Some(method) = toml.method {
+            result.method = method.to_uppercase();
+        }
+        if let Some(headers) = toml.headers {
+            result.headers = headers;
+        }
+        if let Some(body_template) = toml.body_template {
+            result.body_template = Some(body_template);
+        }
+        if let Some(secret) = toml.secret {
+            result.secret = Some(secret);
+        }
+        if let Some(signature_header) = toml.signature_header {
+            result.signature_header = signature_header;
+        }
+        Ok(result)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct EmailConfig {
+    pub smtp_host: String,
+    /// defaults to 25, 587 or 465 depending on `tls`
+    pub smtp_port: Option<u16>,
+    /// none, starttls or tls (implicit tls)
+    pub tls: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+    pub subject_prefix: String,
+}
+
+impl fmt::Debug for EmailConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmailConfig")
+            .field("smtp_host", &self.smtp_host)
+            .field("smtp_port", &self.smtp_port)
+            .field("tls", &self.tls)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| REDACTED))
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("subject_prefix", &self.subject_prefix)
+            .finish()
+    }
+}
+
+impl FromEnv for EmailConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            smtp_host: "".to_owned(),
+            smtp_port: None,
+            tls: "starttls".to_owned(),
+            username: None,
+            password: None,
+            from: "".to_owned(),
+            to: Vec::new(),
+            subject_prefix: "[teritorimon]".to_owned(),
+        })
+    }
+}
+
+impl SelfValidation for EmailConfig {
+    fn validate(&self) -> Result<()> {
+        if self.smtp_host.is_empty() {
+            return Err(anyhow!("email smtp_host is missing..."));
+        }
+        if !["none", "starttls", "tls"].contains(&self.tls.as_str()) {
+            return Err(anyhow!("illegal email tls: {}", self.tls));
+        }
+        if self.username.is_some() != self.password.is_some() {
+            return Err(anyhow!(
+                "email username and password must be set together..."
+            ));
+        }
+        if self.from.is_empty() {
+            return Err(anyhow!("email from is missing..."));
+        }
+        if self.to.is_empty() {
+            return Err(anyhow!("email to is missing..."));
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<EmailToml> for EmailConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: EmailToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(smtp_host) = toml.smtp_host {
+            result.smtp_host = smtp_host;
+        }
+        if let Some(smtp_port) = toml.smtp_port {
+            result.smtp_port = Some(smtp_port);
+        }
+        if let Some(tls) = toml.tls {
+            result.tls = tls.to_lowercase();
+        }
+        if let Some(username) = toml.username {
+            result.username = Some(username);
+        }
+        if let Some(password) = toml.password {
+            result.password = Some(password);
+        }
+        if let Some(from) = toml.from {
+            result.from = from;
+        }
+        if let Some(to) = toml.to {
+            result.to = to;
+        }
+        if let Some(subject_prefix) = toml.subject_prefix {
+            result.subject_prefix = subject_prefix;
+        }
+        Ok(result)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct MatrixConfig {
+    pub homeserver_url: String,
+    pub access_token: String,
+    /// the room id (e.g. `!abcdefg:example.com`), the bot user must be joined to the room
+    pub room_id: String,
+}
+
+impl fmt::Debug for MatrixConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MatrixConfig")
+            .field("homeserver_url", &self.homeserver_url)
+            .field("access_token", &REDACTED)
+            .field("room_id", &self.room_id)
+            .finish()
+    }
+}
+
+impl FromEnv for MatrixConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            homeserver_url: "".to_owned(),
+            access_token: "".to_owned(),
+            room_id: "".to_owned(),
+        })
+    }
+}
+
+impl SelfValidation for MatrixConfig {
+    fn validate(&self) -> Result<()> {
+        if self.homeserver_url.is_empty() {
+            return Err(anyhow!("matrix homeserver_url is missing..."));
+        }
+        if self.access_token.is_empty() {
+            return Err(anyhow!("matrix access_token is missing..."));
+        }
+        if self.room_id.is_empty() {
+            return Err(anyhow!("matrix room_id is missing..."));
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<MatrixToml> for MatrixConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(toml: MatrixToml) -> Result<Self> {
+        let mut result = Self::from_env()?;
+        if let Some(homeserver_url) = toml.homeserver_url {
+            result.homeserver_url = homeserver_url;
+        }
+        if let Some(access_token) = toml.access_token {
+            result.access_token = access_token;
+        }
+        if let Some(room_id) = toml.room_id {
+            result.room_id = room_id;
+        }
+        Ok(result)
+    }
+}
+
+fn validate_severity(severity: &str) -> Result<()> {
+    if !SEVERITIES.contains(&severity) {
+        return Err(anyhow!("illegal severity: {}", severity));
+    }
+    Ok(())
+}
+
+fn validate_alert_kind(kind: &str) -> Result<()> {
+    if !ALERT_KINDS.contains(&kind) {
+        return Err(anyhow!("illegal alert kind: {}", kind));
+    }
+    Ok(())
+}
+
+fn validate_label_matchers(matchers: &BTreeMap<String, String>) -> Result<()> {
+    if let Some(kind) = matchers.get("kind") {
+        validate_alert_kind(kind)?;
+    }
+    if let Some(severity) = matchers.get("severity") {
+        validate_severity(severity)?;
+    }
+    Ok(())
+}
+
 #[allow(dead_code)]
 fn get_env_var<T: FromStr>(var_name: &str, default_value: T) -> Result<T> {
     match env::var(var_name) {
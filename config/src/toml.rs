@@ -1,6 +1,7 @@
 use crate::Result;
 use anyhow::Context;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
@@ -10,6 +11,21 @@ pub struct ApplicationToml {
     pub interval: Option<String>,
     pub checkers: Vec<CheckerToml>,
     pub logger: Option<LoggerToml>,
+    pub notifiers: Option<Vec<NotifierToml>>,
+    pub alerting: Option<AlertingToml>,
+    pub silences: Option<Vec<SilenceToml>>,
+    pub receivers: Option<Vec<ReceiverToml>>,
+    pub route: Option<RouteToml>,
+    pub inhibit_rules: Option<Vec<InhibitRuleToml>>,
+    pub escalations: Option<Vec<EscalationToml>>,
+    pub hooks: Option<Vec<HookToml>>,
+    pub api: Option<ApiToml>,
+    pub metrics: Option<MetricsToml>,
+    pub digests: Option<Vec<DigestToml>>,
+    pub history: Option<HistoryToml>,
+    pub tracing: Option<TracingToml>,
+    pub templates: Option<BTreeMap<String, String>>,
+    pub template_vars: Option<BTreeMap<String, String>>,
 }
 
 impl ApplicationToml {
@@ -49,6 +65,8 @@ pub struct CheckerToml {
     pub missed_block_threshold: Option<String>,
     pub validator_status: Option<bool>,
     pub slashes: Option<bool>,
+    pub notifiers: Option<Vec<NotifierToml>>,
+    pub labels: Option<BTreeMap<String, String>>,
 }
 
 #[derive(Deserialize, Eq, PartialEq, Clone)]
@@ -61,3 +79,186 @@ pub struct LoggerToml {
     pub airbrake_project_key: Option<String>,
     pub airbrake_environment: Option<String>,
 }
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct AlertingToml {
+    pub repeat_interval: Option<String>,
+    pub repeat_intervals: Option<BTreeMap<String, String>>,
+    pub rate_limit: Option<u32>,
+    pub rate_limit_period: Option<String>,
+    pub silences_file: Option<String>,
+    pub hook_concurrency: Option<usize>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct ApiToml {
+    pub listen: Option<String>,
+    pub external_url: Option<String>,
+    pub dashboard: Option<bool>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct MetricsToml {
+    pub listen: Option<String>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct HistoryToml {
+    pub path: Option<String>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct TracingToml {
+    pub endpoint: Option<String>,
+    pub service_name: Option<String>,
+    pub timeout: Option<String>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct DigestToml {
+    pub name: Option<String>,
+    pub schedule: Option<String>,
+    pub timezone: Option<String>,
+    pub receivers: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct SilenceToml {
+    pub kinds: Option<Vec<String>>,
+    pub endpoint: Option<String>,
+    pub validator: Option<String>,
+    pub schedule: Option<String>,
+    pub duration: Option<String>,
+    pub timezone: Option<String>,
+    pub comment: Option<String>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct ReceiverToml {
+    pub name: Option<String>,
+    pub notifiers: Option<Vec<NotifierToml>>,
+    pub templates: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct RouteToml {
+    pub receiver: Option<String>,
+    #[serde(rename = "match")]
+    pub matchers: Option<BTreeMap<String, String>>,
+    #[serde(rename = "continue")]
+    pub continue_matching: Option<bool>,
+    pub routes: Option<Vec<RouteToml>>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct InhibitRuleToml {
+    pub source_match: Option<BTreeMap<String, String>>,
+    pub target_match: Option<BTreeMap<String, String>>,
+    pub equal: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct EscalationToml {
+    #[serde(rename = "match")]
+    pub matchers: Option<BTreeMap<String, String>>,
+    pub steps: Option<Vec<EscalationStepToml>>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct EscalationStepToml {
+    pub after: Option<String>,
+    pub receiver: Option<String>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct HookToml {
+    pub name: Option<String>,
+    pub command: Option<String>,
+    pub args: Option<Vec<String>>,
+    #[serde(rename = "match")]
+    pub matchers: Option<BTreeMap<String, String>>,
+    pub after: Option<String>,
+    pub cooldown: Option<String>,
+    pub timeout: Option<String>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotifierToml {
+    Slack(SlackToml),
+    Discord(DiscordToml),
+    Telegram(TelegramToml),
+    Pagerduty(PagerDutyToml),
+    Opsgenie(OpsgenieToml),
+    Webhook(WebhookToml),
+    Email(EmailToml),
+    Matrix(MatrixToml),
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct SlackToml {
+    pub webhook_url: Option<String>,
+    pub channel: Option<String>,
+    pub username: Option<String>,
+    pub icon_emoji: Option<String>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct DiscordToml {
+    pub webhook_url: Option<String>,
+    pub username: Option<String>,
+    pub avatar_url: Option<String>,
+    pub mention_roles: Option<Vec<String>>,
+    pub max_retries: Option<u32>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct TelegramToml {
+    pub bot_token: Option<String>,
+    pub chat_id: Option<String>,
+    pub api_base_url: Option<String>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct PagerDutyToml {
+    pub routing_key: Option<String>,
+    pub events_url: Option<String>,
+    pub min_severity: Option<String>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct OpsgenieToml {
+    pub api_key: Option<String>,
+    pub api_url: Option<String>,
+    pub team: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct WebhookToml {
+    pub url: Option<String>,
+    pub method: Option<String>,
+    pub headers: Option<BTreeMap<String, String>>,
+    pub body_template: Option<String>,
+    pub secret: Option<String>,
+    pub signature_header: Option<String>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct EmailToml {
+    pub smtp_host: Option<String>,
+    pub smtp_port: Option<u16>,
+    pub tls: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<Vec<String>>,
+    pub subject_prefix: Option<String>,
+}
+
+#[derive(Deserialize, Eq, PartialEq, Clone)]
+pub struct MatrixToml {
+    pub homeserver_url: Option<String>,
+    pub access_token: Option<String>,
+    pub room_id: Option<String>,
+}
@@ -0,0 +1,193 @@
+pub mod trace;
+
+use once_cell::sync::Lazy;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use std::time::Instant;
+
+pub type Result<T> = anyhow::Result<T>;
+
+static REGISTRY: Lazy<Registry> =
+    Lazy::new(|| Registry::new_custom(Some("teritorimon".to_owned()), None).unwrap());
+
+fn register<C: prometheus::core::Collector + Clone + 'static>(collector: C) -> C {
+    REGISTRY
+        .register(Box::new(collector.clone()))
+        .expect("metrics must be registered only once...");
+    collector
+}
+
+/// the latest block height of the teritori daemon endpoint.
+pub static LATEST_BLOCK_HEIGHT: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register(
+        IntGaugeVec::new(
+            Opts::new("latest_block_height", "The latest block height"),
+            &["endpoint"],
+        )
+        .unwrap(),
+    )
+});
+
+pub static SYNCING: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register(
+        IntGaugeVec::new(
+            Opts::new("syncing", "Whether the node is syncing (1) or not (0)"),
+            &["endpoint"],
+        )
+        .unwrap(),
+    )
+});
+
+pub static VALIDATOR_JAILED: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register(
+        IntGaugeVec::new(
+            Opts::new(
+                "validator_jailed",
+                "Whether the validator is jailed (1) or not (0)",
+            ),
+            &["endpoint", "validator"],
+        )
+        .unwrap(),
+    )
+});
+
+pub static VALIDATOR_BOND_STATUS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register(
+        IntGaugeVec::new(
+            Opts::new(
+                "validator_bond_status",
+                "The bond status of the validator (1: unbonded, 2: unbonding, 3: bonded)",
+            ),
+            &["endpoint", "validator"],
+        )
+        .unwrap(),
+    )
+});
+
+pub static MISSED_BLOCKS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register(
+        IntGaugeVec::new(
+            Opts::new(
+                "missed_blocks",
+                "The number of blocks the validator missed to sign within the threshold window",
+            ),
+            &["endpoint", "validator"],
+        )
+        .unwrap(),
+    )
+});
+
+pub static MISSED_BLOCKS_WINDOW: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register(
+        IntGaugeVec::new(
+            Opts::new(
+                "missed_blocks_window",
+                "The number of blocks of the missed block threshold window",
+            ),
+            &["endpoint", "validator"],
+        )
+        .unwrap(),
+    )
+});
+
+pub static SLASH_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(
+        IntCounterVec::new(
+            Opts::new("slash_events_total", "The number of slash events detected"),
+            &["endpoint", "validator"],
+        )
+        .unwrap(),
+    )
+});
+
+pub static GRPC_REQUEST_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register(
+        HistogramVec::new(
+            HistogramOpts::new(
+                "grpc_request_duration_seconds",
+                "The latency of grpc requests to the teritori daemon",
+            ),
+            &["endpoint", "method"],
+        )
+        .unwrap(),
+    )
+});
+
+pub static GRPC_REQUEST_ERRORS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(
+        IntCounterVec::new(
+            Opts::new(
+                "grpc_request_errors_total",
+                "The number of failed grpc requests to the teritori daemon",
+            ),
+            &["endpoint", "method"],
+        )
+        .unwrap(),
+    )
+});
+
+pub static CHECKER_QUEUE_DEPTH: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register(
+        IntGaugeVec::new(
+            Opts::new(
+                "checker_queue_depth",
+                "The number of messages waiting for the checker",
+            ),
+            &["endpoint", "checker"],
+        )
+        .unwrap(),
+    )
+});
+
+/// records the latency and the failure of a grpc request started at `started_at`.
+pub fn observe_grpc_request<T>(
+    endpoint: &str,
+    method: &str,
+    started_at: Instant,
+    result: &Result<T>,
+) {
+    GRPC_REQUEST_DURATION
+        .with_label_values(&[endpoint, method])
+        .observe(started_at.elapsed().as_secs_f64());
+    // exported as 0 until the first failure.
+    let errors = GRPC_REQUEST_ERRORS.with_label_values(&[endpoint, method]);
+    if result.is_err() {
+        errors.inc();
+    }
+}
+
+/// every metric in the prometheus text format.
+pub fn gather() -> String {
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&REGISTRY.gather(), &mut buffer)
+        .expect("metrics must be encoded...");
+    String::from_utf8(buffer).expect("metrics must be utf-8...")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_gather() {
+        LATEST_BLOCK_HEIGHT
+            .with_label_values(&["http://127.0.0.1:9090"])
+            .set(100);
+        observe_grpc_request::<()>(
+            "http://127.0.0.1:9090",
+            "fetch_syncing",
+            Instant::now(),
+            &Err(anyhow!("unavailable")),
+        );
+        let metrics = gather();
+        assert!(metrics
+            .contains("teritorimon_latest_block_height{endpoint=\"http://127.0.0.1:9090\"} 100"));
+        assert!(metrics.contains(
+            "teritorimon_grpc_request_errors_total{endpoint=\"http://127.0.0.1:9090\",method=\"fetch_syncing\"} 1"
+        ));
+        assert!(metrics.contains("teritorimon_grpc_request_duration_seconds_count"));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::Result;
+use opentelemetry::global::{self, BoxedTracer};
+use opentelemetry::sdk::{trace, Resource};
+use opentelemetry::trace::{FutureExt, StatusCode, TraceContextExt, Tracer};
+use opentelemetry::Context;
+use opentelemetry_otlp::WithExportConfig;
+use std::borrow::Cow;
+use std::fmt;
+use std::future::Future;
+use std::result;
+use std::time::Duration;
+
+pub use opentelemetry::KeyValue;
+
+/// spans are dropped unless `install` has been called.
+fn tracer() -> BoxedTracer {
+    global::tracer("teritorimon")
+}
+
+/// exports spans to the opentelemetry collector via otlp (grpc) in batches.
+/// (must be called inside a tokio runtime, which keeps running while exporting)
+pub fn install(endpoint: &str, service_name: &str, timeout: Duration) -> Result<()> {
+    let _tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint)
+                .with_timeout(timeout),
+        )
+        .with_trace_config(
+            trace::config().with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                service_name.to_owned(),
+            )])),
+        )
+        .install_batch(opentelemetry::runtime::Tokio)?;
+    Ok(())
+}
+
+/// exports the spans not exported yet.
+pub fn shutdown() {
+    global::shutdown_tracer_provider();
+}
+
+fn start(name: impl Into<Cow<'static, str>>, attributes: Vec<KeyValue>) -> Context {
+    let tracer = tracer();
+    let span = tracer
+        .span_builder(name)
+        .with_attributes(attributes)
+        .start(&tracer);
+    Context::current_with_span(span)
+}
+
+/// runs the future in a new span, which is a child of the current one.
+pub async fn in_span<F: Future>(
+    name: impl Into<Cow<'static, str>>,
+    attributes: Vec<KeyValue>,
+    future: F,
+) -> F::Output {
+    future.with_context(start(name, attributes)).await
+}
+
+/// runs the future in a new span as `in_span`, and marks the span failed on error.
+pub async fn in_result_span<T, E: fmt::Display, F: Future<Output = result::Result<T, E>>>(
+    name: impl Into<Cow<'static, str>>,
+    attributes: Vec<KeyValue>,
+    future: F,
+) -> result::Result<T, E> {
+    let cx = start(name, attributes);
+    let result = future.with_context(cx.clone()).await;
+    if let Err(err) = result.as_ref() {
+        cx.span().set_status(StatusCode::Error, err.to_string());
+    }
+    result
+}
+
+/// adds the attributes to the current span.
+pub fn set_attributes(attributes: Vec<KeyValue>) {
+    let cx = Context::current();
+    for attribute in attributes {
+        cx.span().set_attribute(attribute);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::anyhow;
+    use opentelemetry::sdk::export::trace::SpanData;
+    use opentelemetry::sdk::trace::{SpanProcessor, TracerProvider};
+    use opentelemetry::trace::TraceResult;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Default)]
+    struct Collector {
+        spans: Arc<Mutex<Vec<SpanData>>>,
+    }
+
+    impl SpanProcessor for Collector {
+        fn on_start(&self, _span: &mut trace::Span, _cx: &Context) {}
+
+        fn on_end(&self, span: SpanData) {
+            self.spans.lock().unwrap().push(span);
+        }
+
+        fn force_flush(&self) -> TraceResult<()> {
+            Ok(())
+        }
+
+        fn shutdown(&mut self) -> TraceResult<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_result_span() {
+        let collector = Collector::default();
+        let spans = collector.spans.clone();
+        global::set_tracer_provider(
+            TracerProvider::builder()
+                .with_span_processor(collector)
+                .build(),
+        );
+        let result: Result<()> = in_result_span(
+            "CheckManager::next",
+            vec![KeyValue::new("endpoint", "http://127.0.0.1:9090")],
+            async {
+                set_attributes(vec![KeyValue::new("latest_height", 100)]);
+                in_result_span(
+                    "TeritoridClient::fetch_latest_block",
+                    vec![KeyValue::new("endpoint", "http://127.0.0.1:9090")],
+                    async { Err(anyhow!("unavailable")) },
+                )
+                .await
+            },
+        )
+        .await;
+        assert!(result.is_err());
+
+        let spans = spans.lock().unwrap();
+        assert_eq!(spans.len(), 2);
+        let (request, next) = (&spans[0], &spans[1]);
+        assert_eq!(request.name, "TeritoridClient::fetch_latest_block");
+        assert_eq!(next.name, "CheckManager::next");
+        assert_eq!(request.parent_span_id, next.span_context.span_id());
+        assert_eq!(
+            request.span_context.trace_id(),
+            next.span_context.trace_id()
+        );
+        assert_eq!(request.status_code, StatusCode::Error);
+        assert_eq!(next.status_code, StatusCode::Error);
+        assert_eq!(next.status_message, "unavailable");
+        assert!(next
+            .attributes
+            .iter()
+            .any(|(key, value)| key.as_str() == "latest_height" && value.as_str() == "100"));
+    }
+}
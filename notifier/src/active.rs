@@ -0,0 +1,52 @@
+use crate::Alert;
+use serde_json::{json, Value};
+use std::sync::{Arc, RwLock};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActiveAlert {
+    pub alert: Alert,
+    pub acknowledged: bool,
+}
+
+impl ActiveAlert {
+    pub fn to_json(&self) -> Value {
+        let mut result = self.alert.to_json();
+        result["acknowledged"] = json!(self.acknowledged);
+        result
+    }
+}
+
+/// a snapshot of the firing conditions and escalating events the dispatcher publishes,
+/// e.g. to list and acknowledge them through the http api.
+#[derive(Clone, Debug, Default)]
+pub struct ActiveAlerts {
+    inner: Arc<RwLock<Vec<ActiveAlert>>>,
+}
+
+impl ActiveAlerts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn replace(&self, mut alerts: Vec<ActiveAlert>) {
+        alerts.sort_by(|a, b| {
+            let started_at = |alert: &Alert| alert.started_at.unwrap_or(alert.timestamp);
+            started_at(&a.alert).cmp(&started_at(&b.alert))
+        });
+        *self.inner.write().unwrap() = alerts;
+    }
+
+    /// the active alerts in the order they started.
+    pub fn list(&self) -> Vec<ActiveAlert> {
+        self.inner.read().unwrap().clone()
+    }
+
+    pub fn get(&self, id: &str) -> Option<ActiveAlert> {
+        self.inner
+            .read()
+            .unwrap()
+            .iter()
+            .find(|active| active.alert.id() == id)
+            .cloned()
+    }
+}
@@ -0,0 +1,306 @@
+use anyhow::anyhow;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use subtle_encoding::hex;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    IsSyncing,
+    NewProposal,
+    MissedBlock,
+    ValidatorStatus,
+    Slashes,
+    /// the check itself could not be performed (e.g. grpc request failure)
+    CheckFailure,
+    /// the teritori daemon does not respond to the check manager
+    Unreachable,
+    /// a scheduled summary report
+    Digest,
+}
+
+impl AlertKind {
+    pub const ALL: [AlertKind; 8] = [
+        Self::IsSyncing,
+        Self::NewProposal,
+        Self::MissedBlock,
+        Self::ValidatorStatus,
+        Self::Slashes,
+        Self::CheckFailure,
+        Self::Unreachable,
+        Self::Digest,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::IsSyncing => "is_syncing",
+            Self::NewProposal => "new_proposal",
+            Self::MissedBlock => "missed_block",
+            Self::ValidatorStatus => "validator_status",
+            Self::Slashes => "slashes",
+            Self::CheckFailure => "check_failure",
+            Self::Unreachable => "unreachable",
+            Self::Digest => "digest",
+        }
+    }
+
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            Self::IsSyncing => Severity::Error,
+            Self::NewProposal => Severity::Info,
+            Self::MissedBlock => Severity::Error,
+            Self::ValidatorStatus => Severity::Critical,
+            Self::Slashes => Severity::Critical,
+            Self::CheckFailure => Severity::Error,
+            Self::Unreachable => Severity::Error,
+            Self::Digest => Severity::Info,
+        }
+    }
+
+    /// whether the kind describes a condition which lasts until it is resolved,
+    /// rather than a one-shot event like a new proposal.
+    pub fn is_stateful(&self) -> bool {
+        matches!(
+            self,
+            Self::IsSyncing | Self::MissedBlock | Self::ValidatorStatus | Self::Unreachable
+        )
+    }
+}
+
+impl fmt::Display for AlertKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AlertKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|kind| kind.as_str() == s)
+            .copied()
+            .ok_or_else(|| anyhow!("illegal alert kind: {}", s))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+    Critical,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Severity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(Self::Info),
+            "warning" => Ok(Self::Warning),
+            "error" => Ok(Self::Error),
+            "critical" => Ok(Self::Critical),
+            _ => Err(anyhow!("illegal severity: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatus {
+    Firing,
+    /// the condition which fired the alert has cleared
+    Resolved,
+}
+
+impl AlertStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Firing => "firing",
+            Self::Resolved => "resolved",
+        }
+    }
+}
+
+impl fmt::Display for AlertStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub severity: Severity,
+    pub status: AlertStatus,
+    pub endpoint: String,
+    pub validator: Option<String>,
+    pub height: Option<i64>,
+    pub message: String,
+    pub fields: BTreeMap<String, String>,
+    /// custom labels of the checker (e.g. `team = 'core'`) to route alerts with.
+    pub labels: BTreeMap<String, String>,
+    pub timestamp: DateTime<Utc>,
+    /// when the condition started firing. (set by the alert lifecycle)
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+impl Alert {
+    pub fn new(kind: AlertKind, endpoint: &str, message: String) -> Self {
+        Self {
+            kind,
+            severity: kind.default_severity(),
+            status: AlertStatus::Firing,
+            endpoint: endpoint.to_owned(),
+            validator: None,
+            height: None,
+            message,
+            fields: BTreeMap::new(),
+            labels: BTreeMap::new(),
+            timestamp: Utc::now(),
+            started_at: None,
+        }
+    }
+    pub fn resolved(kind: AlertKind, endpoint: &str, message: String) -> Self {
+        let mut result = Self::new(kind, endpoint, message);
+        result.status = AlertStatus::Resolved;
+        result
+    }
+    /// a stable key to identify the same condition across firing/resolved alerts.
+    pub fn dedup_key(&self) -> String {
+        format!(
+            "{}/{}/{}",
+            self.kind.as_str(),
+            self.validator.as_deref().unwrap_or_default(),
+            self.endpoint.as_str()
+        )
+    }
+    /// a short id of the condition derived from the dedup key, e.g. to acknowledge it with.
+    pub fn id(&self) -> String {
+        let digest = Sha256::digest(self.dedup_key().as_bytes());
+        String::from_utf8_lossy(&hex::encode(&digest[..6])).into_owned()
+    }
+    /// the key to deduplicate the alert with in incident management tools. one-shot events
+    /// (e.g. slashes) are never resolved, so each gets a key of its own instead of the dedup key.
+    pub fn event_key(&self) -> String {
+        if self.kind.is_stateful() {
+            self.dedup_key()
+        } else {
+            format!("{}/{}", self.id(), self.timestamp.timestamp_millis())
+        }
+    }
+    pub fn is_resolved(&self) -> bool {
+        self.status == AlertStatus::Resolved
+    }
+    /// whether the alert reminds of a condition which has been firing for a while.
+    pub fn is_reminder(&self) -> bool {
+        !self.is_resolved()
+            && self
+                .started_at
+                .map(|started_at| started_at < self.timestamp)
+                .unwrap_or_default()
+    }
+    /// the custom labels with the built-in `kind`, `severity`, `status`, `endpoint` and `validator`.
+    pub fn all_labels(&self) -> BTreeMap<String, String> {
+        let mut result = self.labels.clone();
+        result.insert("kind".to_owned(), self.kind.as_str().to_owned());
+        result.insert("severity".to_owned(), self.severity.as_str().to_owned());
+        result.insert("status".to_owned(), self.status.as_str().to_owned());
+        result.insert("endpoint".to_owned(), self.endpoint.clone());
+        if let Some(validator) = self.validator.as_deref() {
+            result.insert("validator".to_owned(), validator.to_owned());
+        }
+        result
+    }
+    /// how long the condition has been firing (or had fired if resolved).
+    pub fn duration(&self) -> Option<Duration> {
+        self.started_at
+            .map(|started_at| self.timestamp.signed_duration_since(started_at))
+    }
+    /// every field of the alert as json, e.g. to render templates with.
+    pub fn to_json(&self) -> Value {
+        let mut result = serde_json::to_value(self).expect("alert must be serializable");
+        result["id"] = json!(self.id());
+        result["dedup_key"] = json!(self.dedup_key());
+        result
+    }
+    pub fn severity(&mut self, severity: Severity) -> &mut Self {
+        self.severity = severity;
+        self
+    }
+    pub fn validator(&mut self, validator: &str) -> &mut Self {
+        self.validator = Some(validator.to_owned());
+        self
+    }
+    pub fn height(&mut self, height: i64) -> &mut Self {
+        self.height = Some(height);
+        self
+    }
+    pub fn field<V: ToString>(&mut self, key: &str, value: V) -> &mut Self {
+        self.fields.insert(key.to_owned(), value.to_string());
+        self
+    }
+}
+
+/// formats a duration like `1h 2m 3s`.
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.num_seconds().max(0);
+    let (days, hours, minutes, seconds) = (
+        seconds / 86_400,
+        seconds % 86_400 / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60,
+    );
+    let mut parts = Vec::new();
+    if days > 0 {
+        parts.push(format!("{}d", days));
+    }
+    if hours > 0 {
+        parts.push(format!("{}h", hours));
+    }
+    if minutes > 0 {
+        parts.push(format!("{}m", minutes));
+    }
+    if seconds > 0 || parts.is_empty() {
+        parts.push(format!("{}s", seconds));
+    }
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::seconds(0)), "0s");
+        assert_eq!(format_duration(Duration::seconds(59)), "59s");
+        assert_eq!(format_duration(Duration::seconds(3_600)), "1h");
+        assert_eq!(format_duration(Duration::seconds(93_784)), "1d 2h 3m 4s");
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{Alert, AlertKind};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use config::configs::DigestConfig;
+use cron::Schedule;
+use std::collections::BTreeMap;
+
+/// what happened to a validator (precisely, the teritori daemon endpoint monitoring it) in a period.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ValidatorStats {
+    signed_blocks: u64,
+    missed_blocks: u64,
+    new_proposals: Vec<String>,
+    slash_events: u64,
+    status_changes: u64,
+    sync_incidents: u64,
+    unreachable_incidents: u64,
+    /// the number of firing alerts by kind.
+    alerts: BTreeMap<&'static str, u64>,
+}
+
+/// collects statistics per validator and reports them on schedule (e.g. daily or weekly).
+#[derive(Debug)]
+pub struct Digest {
+    name: String,
+    schedule: Schedule,
+    timezone: Tz,
+    /// receivers to send the report to. (the global notifiers if empty)
+    pub receivers: Vec<String>,
+    /// the names of the validators by endpoint.
+    validators: BTreeMap<String, String>,
+    since: DateTime<Utc>,
+    next_at: Option<DateTime<Utc>>,
+    stats: BTreeMap<String, ValidatorStats>,
+}
+
+impl Digest {
+    pub fn from_config(config: &DigestConfig, now: DateTime<Utc>) -> Self {
+        let mut result = Self {
+            name: config.name.clone(),
+            schedule: config.get_schedule(),
+            timezone: config.get_timezone(),
+            receivers: config.receivers.clone(),
+            validators: BTreeMap::new(),
+            since: now,
+            next_at: None,
+            stats: BTreeMap::new(),
+        };
+        result.next_at = result.next_after(now);
+        result
+    }
+
+    fn next_after(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.schedule
+            .after(&now.with_timezone(&self.timezone))
+            .next()
+            .map(|next_at| next_at.with_timezone(&Utc))
+    }
+
+    /// reports the validator monitored through the endpoint even if nothing happened.
+    pub fn add_validator(&mut self, endpoint: &str, name: &str) -> &mut Self {
+        self.validators.insert(endpoint.to_owned(), name.to_owned());
+        self.stats.entry(endpoint.to_owned()).or_default();
+        self
+    }
+
+    /// counts the alerts the alert lifecycle let through. (reminders are not counted)
+    pub fn record(&mut self, alert: &Alert) {
+        if alert.kind == AlertKind::Digest || alert.is_reminder() {
+            return;
+        }
+        let stats = self.stats.entry(alert.endpoint.clone()).or_default();
+        match alert.kind {
+            AlertKind::NewProposal => stats.new_proposals.push(
+                alert
+                    .fields
+                    .get("proposal_id")
+                    .map(|id| format!("#{}", id))
+                    .unwrap_or_else(|| "?".to_owned()),
+            ),
+            AlertKind::Slashes if !alert.is_resolved() => {
+                stats.slash_events += alert
+                    .fields
+                    .get("slash_events")
+                    .and_then(|count| count.parse().ok())
+                    .unwrap_or(1)
+            }
+            AlertKind::ValidatorStatus => stats.status_changes += 1,
+            AlertKind::IsSyncing if !alert.is_resolved() => stats.sync_incidents += 1,
+            AlertKind::Unreachable if !alert.is_resolved() => stats.unreachable_incidents += 1,
+            _ => {}
+        }
+        if !alert.is_resolved() {
+            *stats.alerts.entry(alert.kind.as_str()).or_default() += 1;
+        }
+    }
+
+    /// counts a block the validator signed or missed.
+    pub fn record_signature(&mut self, endpoint: &str, signed: bool) {
+        let stats = self.stats.entry(endpoint.to_owned()).or_default();
+        if signed {
+            stats.signed_blocks += 1;
+        } else {
+            stats.missed_blocks += 1;
+        }
+    }
+
+    /// the report of the period if it is time to send, which starts the next period.
+    pub fn due(&mut self, now: DateTime<Utc>) -> Option<Alert> {
+        if self.next_at? > now {
+            return None;
+        }
+        let report = self.report(now);
+        self.since = now;
+        self.next_at = self.next_after(now);
+        for stats in self.stats.values_mut() {
+            *stats = ValidatorStats::default();
+        }
+        Some(report)
+    }
+
+    fn report(&self, until: DateTime<Utc>) -> Alert {
+        let format = "%Y-%m-%d %H:%M";
+        let mut lines = vec![format!(
+            "{} {} - {} ({})",
+            self.name,
+            self.since.with_timezone(&self.timezone).format(format),
+            until.with_timezone(&self.timezone).format(format),
+            self.timezone.name()
+        )];
+        for (endpoint, stats) in &self.stats {
+            lines.push("".to_owned());
+            match self.validators.get(endpoint) {
+                Some(name) => lines.push(format!("{} ({})", name, endpoint)),
+                None => lines.push(endpoint.clone()),
+            }
+            let blocks = stats.signed_blocks + stats.missed_blocks;
+            if blocks > 0 {
+                lines.push(format!(
+                    "- blocks: {} signed / {} missed ({:.2}% signed)",
+                    stats.signed_blocks,
+                    stats.missed_blocks,
+                    stats.signed_blocks as f64 * 100.0 / blocks as f64
+                ));
+            }
+            lines.push(format!(
+                "- new proposals: {}",
+                if stats.new_proposals.is_empty() {
+                    "none".to_owned()
+                } else {
+                    stats.new_proposals.join(", ")
+                }
+            ));
+            lines.push(format!("- slash events: {}", stats.slash_events));
+            lines.push(format!("- status changes: {}", stats.status_changes));
+            lines.push(format!(
+                "- sync incidents: {} (unreachable: {})",
+                stats.sync_incidents, stats.unreachable_incidents
+            ));
+            let alerts: Vec<String> = stats
+                .alerts
+                .iter()
+                .map(|(kind, count)| format!("{} {}", kind, count))
+                .collect();
+            lines.push(format!(
+                "- alerts: {}",
+                if alerts.is_empty() {
+                    "none".to_owned()
+                } else {
+                    alerts.join(", ")
+                }
+            ));
+        }
+        let mut alert = Alert::new(AlertKind::Digest, "", lines.join("\n"));
+        alert.timestamp = until;
+        alert
+            .field("name", self.name.as_str())
+            .field("since", self.since.to_rfc3339())
+            .field("until", until.to_rfc3339());
+        alert
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::Duration;
+
+    const ENDPOINT: &str = "http://127.0.0.1:9090";
+
+    #[test]
+    fn test_report() {
+        let now = DateTime::parse_from_rfc3339("2022-10-01T00:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut digest = Digest::from_config(
+            &DigestConfig {
+                name: "daily digest".to_owned(),
+                schedule: "0 0 9 * * *".to_owned(),
+                timezone: "Asia/Tokyo".to_owned(),
+                receivers: Vec::new(),
+            },
+            now,
+        );
+        digest.add_validator(ENDPOINT, "torivaloper1xxx");
+        for signed in [true, true, true, false] {
+            digest.record_signature(ENDPOINT, signed);
+        }
+        let mut proposal = Alert::new(AlertKind::NewProposal, ENDPOINT, "proposal".to_owned());
+        proposal.field("proposal_id", 12);
+        digest.record(&proposal);
+        let mut syncing = Alert::new(AlertKind::IsSyncing, ENDPOINT, "syncing".to_owned());
+        syncing.started_at = Some(syncing.timestamp);
+        digest.record(&syncing);
+        // reminders and resolutions are not incidents.
+        let mut reminder = syncing.clone();
+        reminder.timestamp += Duration::hours(4);
+        digest.record(&reminder);
+        digest.record(&Alert::resolved(
+            AlertKind::IsSyncing,
+            ENDPOINT,
+            "synced".to_owned(),
+        ));
+
+        // 09:00 in Tokyo is 00:00 UTC.
+        assert!(digest.due(now + Duration::hours(23)).is_none());
+        let report = digest.due(now + Duration::hours(24)).unwrap();
+        assert_eq!(report.kind, AlertKind::Digest);
+        assert_eq!(
+            report.message,
+            "daily digest 2022-10-01 09:30 - 2022-10-02 09:30 (Asia/Tokyo)
+
+torivaloper1xxx (http://127.0.0.1:9090)
+- blocks: 3 signed / 1 missed (75.00% signed)
+- new proposals: #12
+- slash events: 0
+- status changes: 0
+- sync incidents: 1 (unreachable: 0)
+- alerts: is_syncing 1, new_proposal 1"
+        );
+        // the next period starts from scratch.
+        let report = digest.due(now + Duration::hours(48)).unwrap();
+        assert!(report.message.contains("- alerts: none"));
+        assert!(!report.message.contains("- blocks:"));
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{Alert, Notifier, Result, Severity};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use config::configs::DiscordConfig;
+use logger::prelude::*;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// the longest to wait for the rate limit to reset, which delays the other deliveries meanwhile.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(10);
+
+/// posts alerts to a discord webhook as embeds.
+pub struct DiscordNotifier {
+    config: DiscordConfig,
+    client: reqwest::Client,
+    /// discord tells us when the current rate limit bucket resets.
+    blocked_until: Mutex<Option<Instant>>,
+}
+
+impl DiscordNotifier {
+    pub fn new(config: DiscordConfig) -> Self {
+        Self {
+            config,
+            client: crate::http_client(),
+            blocked_until: Mutex::new(None),
+        }
+    }
+
+    fn color(alert: &Alert) -> u32 {
+        if alert.is_resolved() {
+            return 0x2ecc71;
+        }
+        match alert.severity {
+            Severity::Info => 0x3498db,
+            Severity::Warning => 0xf1c40f,
+            Severity::Error => 0xe67e22,
+            Severity::Critical => 0xe74c3c,
+        }
+    }
+
+    fn build_payload(&self, alert: &Alert) -> Value {
+        let mut fields = vec![
+            json!({"name": "Check", "value": alert.kind.as_str(), "inline": true}),
+            json!({"name": "Endpoint", "value": alert.endpoint.as_str(), "inline": true}),
+        ];
+        if let Some(validator) = alert.validator.as_deref() {
+            fields.push(json!({"name": "Validator", "value": validator, "inline": false}));
+        }
+        if let Some(height) = alert.height {
+            fields.push(json!({"name": "Height", "value": height.to_string(), "inline": true}));
+        }
+        for (key, value) in &alert.fields {
+            if !value.is_empty() {
+                fields.push(json!({"name": key, "value": value, "inline": true}));
+            }
+        }
+        // discord accepts at most 25 fields per embed.
+        fields.truncate(25);
+        let label = if alert.is_resolved() {
+            "RESOLVED".to_owned()
+        } else {
+            alert.severity.as_str().to_uppercase()
+        };
+        let mut payload = json!({
+            "embeds": [{
+                "title": format!("[{}] {}", label, alert.kind.as_str()),
+                "description": alert.message.as_str(),
+                "color": Self::color(alert),
+                "timestamp": alert.timestamp.to_rfc3339(),
+                "fields": fields,
+            }],
+            "allowed_mentions": {"parse": [], "roles": []},
+        });
+        if alert.severity == Severity::Critical
+            && !alert.is_resolved()
+            && !self.config.mention_roles.is_empty()
+        {
+            let mentions: Vec<String> = self
+                .config
+                .mention_roles
+                .iter()
+                .map(|role| format!("<@&{}>", role))
+                .collect();
+            payload["content"] = json!(mentions.join(" "));
+            payload["allowed_mentions"]["roles"] = json!(self.config.mention_roles);
+        }
+        if let Some(username) = self.config.username.as_deref() {
+            payload["username"] = json!(username);
+        }
+        if let Some(avatar_url) = self.config.avatar_url.as_deref() {
+            payload["avatar_url"] = json!(avatar_url);
+        }
+        payload
+    }
+
+    async fn wait_for_rate_limit(&self) -> Result<()> {
+        let blocked_until = *self.blocked_until.lock().unwrap();
+        if let Some(blocked_until) = blocked_until {
+            let now = Instant::now();
+            if blocked_until > now {
+                let wait = blocked_until - now;
+                if wait > MAX_RETRY_AFTER {
+                    return Err(anyhow!(
+                        "discord rate limit exceeded for {} seconds",
+                        wait.as_secs_f64()
+                    ));
+                }
+                tokio::time::sleep(wait).await;
+            }
+        }
+        Ok(())
+    }
+
+    fn update_rate_limit(&self, headers: &HeaderMap) {
+        let remaining = header_value(headers, "x-ratelimit-remaining");
+        let reset_after = header_value(headers, "x-ratelimit-reset-after");
+        let mut blocked_until = self.blocked_until.lock().unwrap();
+        *blocked_until = match (remaining, reset_after) {
+            (Some(remaining), Some(reset_after)) if remaining <= 0.0 => {
+                Some(Instant::now() + Duration::from_secs_f64(reset_after))
+            }
+            _ => None,
+        };
+    }
+}
+
+fn header_value(headers: &HeaderMap, name: &str) -> Option<f64> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    fn name(&self) -> &str {
+        "discord"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        let payload = self.build_payload(alert);
+        let mut retries = 0;
+        loop {
+            self.wait_for_rate_limit().await?;
+            let response = self
+                .client
+                .post(self.config.webhook_url.as_str())
+                .json(&payload)
+                .send()
+                .await?;
+            let status = response.status();
+            self.update_rate_limit(response.headers());
+            if status == StatusCode::TOO_MANY_REQUESTS && retries < self.config.max_retries {
+                let header_retry_after = header_value(response.headers(), "retry-after");
+                let body: Value = response.json().await.unwrap_or_default();
+                let retry_after = body["retry_after"]
+                    .as_f64()
+                    .or(header_retry_after)
+                    .unwrap_or(1.0);
+                if retry_after > MAX_RETRY_AFTER.as_secs_f64() {
+                    return Err(anyhow!(
+                        "discord rate limit exceeded, gave up retrying after {} seconds",
+                        retry_after
+                    ));
+                }
+                warn!(
+                    "discord rate limit exceeded, will retry after {} seconds",
+                    retry_after
+                );
+                tokio::time::sleep(Duration::from_secs_f64(retry_after)).await;
+                retries += 1;
+                continue;
+            }
+            if !status.is_success() {
+                return Err(anyhow!(
+                    "unexpected response from discord status_code: {}, message: {}",
+                    status,
+                    response.text().await.unwrap_or_default()
+                ));
+            }
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::{self, StubResponse};
+    use crate::AlertKind;
+
+    fn new_config(webhook_url: String) -> DiscordConfig {
+        DiscordConfig {
+            webhook_url,
+            username: None,
+            avatar_url: None,
+            mention_roles: vec!["1234".to_owned()],
+            max_retries: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notify_mentions_roles_on_critical() {
+        let (url, request) = testing::serve_once(204, "").await;
+        let notifier = DiscordNotifier::new(new_config(url));
+        let mut alert = Alert::new(
+            AlertKind::ValidatorStatus,
+            "http://127.0.0.1:9090",
+            "validator is not healthy...".to_owned(),
+        );
+        alert.validator("torivaloper1xxx").field("jailed", true);
+        notifier.notify(&alert).await.unwrap();
+
+        let body: Value = serde_json::from_str(request.await.unwrap().body.as_str()).unwrap();
+        assert_eq!(body["content"], "<@&1234>");
+        assert_eq!(body["allowed_mentions"]["roles"][0], "1234");
+        assert_eq!(body["embeds"][0]["color"], 0xe74c3c);
+    }
+
+    #[tokio::test]
+    async fn test_notify_retries_on_rate_limit() {
+        let (url, requests) = testing::serve(vec![
+            StubResponse::new(429, r#"{"message": "You are being rate limited.", "retry_after": 0.05, "global": false}"#),
+            StubResponse::new(204, ""),
+        ])
+        .await;
+        let notifier = DiscordNotifier::new(new_config(url));
+        let alert = Alert::new(
+            AlertKind::IsSyncing,
+            "http://127.0.0.1:9090",
+            "syncing".to_owned(),
+        );
+        notifier.notify(&alert).await.unwrap();
+
+        let requests = requests.await.unwrap();
+        assert_eq!(requests.len(), 2);
+        let body: Value = serde_json::from_str(requests[1].body.as_str()).unwrap();
+        assert!(body.get("content").is_none());
+        assert_eq!(body["embeds"][0]["color"], 0xe67e22);
+    }
+
+    #[tokio::test]
+    async fn test_notify_gives_up_on_long_rate_limit() {
+        let (url, requests) = testing::serve(vec![StubResponse::new(
+            429,
+            r#"{"message": "You are being rate limited.", "retry_after": 60.0, "global": false}"#,
+        )])
+        .await;
+        let notifier = DiscordNotifier::new(new_config(url));
+        let alert = Alert::new(
+            AlertKind::IsSyncing,
+            "http://127.0.0.1:9090",
+            "syncing".to_owned(),
+        );
+        assert!(notifier.notify(&alert).await.is_err());
+        assert_eq!(requests.await.unwrap().len(), 1);
+    }
+}
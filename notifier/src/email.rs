@@ -0,0 +1,202 @@
+use crate::{Alert, Notifier, Result};
+use async_trait::async_trait;
+use config::configs::EmailConfig;
+use handlebars::html_escape;
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::transport::smtp::client::{Tls, TlsParameters};
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::time::Duration;
+
+/// sends alerts as multipart (plain text + html) emails over smtp.
+pub struct EmailNotifier {
+    config: EmailConfig,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl EmailNotifier {
+    pub fn new(config: EmailConfig) -> Result<Self> {
+        let from = config.from.parse()?;
+        let to = config
+            .to
+            .iter()
+            .map(|to| to.parse())
+            .collect::<std::result::Result<Vec<Mailbox>, _>>()?;
+        let (tls, default_port) = match config.tls.as_str() {
+            "tls" => (
+                Tls::Wrapper(TlsParameters::new(config.smtp_host.clone())?),
+                465,
+            ),
+            "starttls" => (
+                Tls::Required(TlsParameters::new(config.smtp_host.clone())?),
+                587,
+            ),
+            _ => (Tls::None, 25),
+        };
+        let mut builder =
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(config.smtp_host.as_str())
+                .port(config.smtp_port.unwrap_or(default_port))
+                .tls(tls)
+                .timeout(Some(Duration::from_secs(30)));
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+        Ok(Self {
+            config,
+            from,
+            to,
+            transport: builder.build(),
+        })
+    }
+
+    fn label(alert: &Alert) -> String {
+        if alert.is_resolved() {
+            "RESOLVED".to_owned()
+        } else {
+            alert.severity.as_str().to_uppercase()
+        }
+    }
+
+    fn details(alert: &Alert) -> Vec<(String, String)> {
+        let mut details = vec![("endpoint".to_owned(), alert.endpoint.clone())];
+        if let Some(validator) = alert.validator.as_deref() {
+            details.push(("validator".to_owned(), validator.to_owned()));
+        }
+        if let Some(height) = alert.height {
+            details.push(("height".to_owned(), height.to_string()));
+        }
+        for (key, value) in &alert.fields {
+            details.push((key.clone(), value.clone()));
+        }
+        details.push(("time".to_owned(), alert.timestamp.to_rfc3339()));
+        details
+    }
+
+    fn build_subject(&self, alert: &Alert) -> String {
+        let mut subject = format!("[{}] {}", Self::label(alert), alert.kind.as_str());
+        if let Some(validator) = alert.validator.as_deref() {
+            subject.push_str(format!(" {}", validator).as_str());
+        }
+        if self.config.subject_prefix.is_empty() {
+            subject
+        } else {
+            format!("{} {}", self.config.subject_prefix, subject)
+        }
+    }
+
+    fn build_plain_body(alert: &Alert) -> String {
+        let mut lines = vec![
+            format!("[{}] {}", Self::label(alert), alert.kind.as_str()),
+            alert.message.clone(),
+            "".to_owned(),
+        ];
+        for (key, value) in Self::details(alert) {
+            lines.push(format!("{}: {}", key, value));
+        }
+        lines.join("\n")
+    }
+
+    fn build_html_body(alert: &Alert) -> String {
+        let color = if alert.is_resolved() {
+            "#2ecc71"
+        } else {
+            "#e74c3c"
+        };
+        let rows: Vec<String> = Self::details(alert)
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    "<tr><th align=\"left\">{}</th><td>{}</td></tr>",
+                    html_escape(key),
+                    html_escape(value)
+                )
+            })
+            .collect();
+        format!(
+            "<html><body><h2 style=\"color: {}\">[{}] {}</h2><p>{}</p><table>{}</table></body></html>",
+            color,
+            Self::label(alert),
+            html_escape(alert.kind.as_str()),
+            html_escape(alert.message.as_str()),
+            rows.join("")
+        )
+    }
+
+    fn build_message(&self, alert: &Alert) -> Result<Message> {
+        let mut builder = Message::builder()
+            .from(self.from.clone())
+            .subject(self.build_subject(alert));
+        for to in &self.to {
+            builder = builder.to(to.clone());
+        }
+        Ok(builder.multipart(MultiPart::alternative_plain_html(
+            Self::build_plain_body(alert),
+            Self::build_html_body(alert),
+        ))?)
+    }
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    fn name(&self) -> &str {
+        "email"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        let message = self.build_message(alert)?;
+        self.transport.send(message).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing;
+    use crate::AlertKind;
+
+    #[tokio::test]
+    async fn test_notify() {
+        let (port, mail) = testing::serve_smtp().await;
+        let notifier = EmailNotifier::new(EmailConfig {
+            smtp_host: "127.0.0.1".to_owned(),
+            smtp_port: Some(port),
+            tls: "none".to_owned(),
+            username: None,
+            password: None,
+            from: "teritorimon <alerts@example.com>".to_owned(),
+            to: vec![
+                "finance@example.com".to_owned(),
+                "Delegator Relations <dr@example.com>".to_owned(),
+            ],
+            subject_prefix: "[teritorimon]".to_owned(),
+        })
+        .unwrap();
+        let mut alert = Alert::new(
+            AlertKind::Slashes,
+            "http://127.0.0.1:9090",
+            "validator has been slashed <fraction: 0.01>".to_owned(),
+        );
+        alert.validator("torivaloper1xxx").height(100);
+        notifier.notify(&alert).await.unwrap();
+
+        let mail = mail.await.unwrap();
+        assert_eq!(mail.mail_from, "alerts@example.com");
+        assert_eq!(
+            mail.rcpt_to,
+            vec![
+                "finance@example.com".to_owned(),
+                "dr@example.com".to_owned()
+            ]
+        );
+        assert!(mail
+            .data
+            .contains("Subject: [teritorimon] [CRITICAL] slashes torivaloper1xxx"));
+        assert!(mail.data.contains("Content-Type: text/plain"));
+        assert!(mail.data.contains("Content-Type: text/html"));
+        assert!(mail.data.contains("height: 100"));
+        assert!(mail.data.contains("&lt;fraction: 0.01&gt;"));
+    }
+}
@@ -0,0 +1,264 @@
+use crate::Alert;
+use config::configs::EscalationConfig;
+use logger::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EscalationStep {
+    /// how long after the alert started firing to escalate.
+    pub after: Duration,
+    pub receiver: String,
+}
+
+/// a chain of receivers which alerts matching the labels are escalated to.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EscalationPolicy {
+    pub matchers: BTreeMap<String, String>,
+    pub steps: Vec<EscalationStep>,
+}
+
+impl EscalationPolicy {
+    pub fn from_config(config: &EscalationConfig) -> Self {
+        Self {
+            matchers: config.matchers.clone(),
+            steps: config
+                .steps
+                .iter()
+                .map(|step| EscalationStep {
+                    after: step.get_after(),
+                    receiver: step.receiver.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn matches(&self, alert: &Alert) -> bool {
+        let labels = alert.all_labels();
+        self.matchers
+            .iter()
+            .all(|(key, value)| labels.get(key) == Some(value))
+    }
+}
+
+#[derive(Debug)]
+struct Escalation {
+    alert: Alert,
+    policy: usize,
+    started_at: Instant,
+    /// the index of the step to escalate next.
+    next_step: usize,
+    acknowledged: bool,
+}
+
+/// escalates delivered alerts to the next receivers of the matching policy step by step,
+/// while they keep firing and nobody acknowledges them.
+///
+/// conditions (e.g. validator status) stop escalating when resolved, and one-shot
+/// events (e.g. slashes) when acknowledged or escalated to the last receiver.
+#[derive(Debug, Default)]
+pub struct Escalator {
+    policies: Vec<EscalationPolicy>,
+    /// escalations in progress by dedup key.
+    escalations: HashMap<String, Escalation>,
+}
+
+impl Escalator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_policy(&mut self, policy: EscalationPolicy) -> &mut Self {
+        self.policies.push(policy);
+        self
+    }
+
+    /// starts escalating a firing alert, or stops escalating a resolved one.
+    /// returns the receivers the alert has been escalated to, which should know the resolution too.
+    pub fn observe(&mut self, alert: &Alert, now: Instant) -> Vec<String> {
+        let key = alert.dedup_key();
+        if alert.is_resolved() {
+            let escalation = match self.escalations.remove(&key) {
+                Some(escalation) => escalation,
+                None => return Vec::new(),
+            };
+            info!(
+                kind = alert.kind.as_str(),
+                "escalation of {} is stopped since it has been resolved", key
+            );
+            return self.policies[escalation.policy].steps[..escalation.next_step]
+                .iter()
+                .map(|step| step.receiver.clone())
+                .collect();
+        }
+        if let Some(escalation) = self.escalations.get_mut(&key) {
+            // keep the latest details for the next steps.
+            escalation.alert = alert.clone();
+            return Vec::new();
+        }
+        if let Some(policy) = self.policies.iter().position(|p| p.matches(alert)) {
+            info!(
+                kind = alert.kind.as_str(),
+                "escalation of {} is started", key
+            );
+            self.escalations.insert(
+                key,
+                Escalation {
+                    alert: alert.clone(),
+                    policy,
+                    started_at: now,
+                    next_step: 0,
+                    acknowledged: false,
+                },
+            );
+        }
+        Vec::new()
+    }
+
+    /// stops escalating the alert. returns false if it is not escalating.
+    pub fn acknowledge(&mut self, dedup_key: &str) -> bool {
+        let escalation = match self.escalations.get_mut(dedup_key) {
+            Some(escalation) => escalation,
+            None => return false,
+        };
+        escalation.acknowledged = true;
+        info!(
+            kind = escalation.alert.kind.as_str(),
+            "escalation of {} is stopped since it has been acknowledged", dedup_key
+        );
+        if !escalation.alert.kind.is_stateful() {
+            self.escalations.remove(dedup_key);
+        }
+        true
+    }
+
+    /// one-shot events escalating, which are active until acknowledged.
+    pub fn events(&self) -> impl Iterator<Item = &Alert> {
+        self.escalations
+            .values()
+            .filter(|escalation| !escalation.alert.kind.is_stateful())
+            .map(|escalation| &escalation.alert)
+    }
+
+    /// the alerts to escalate now with the receivers of the steps.
+    pub fn due(&mut self, now: Instant) -> Vec<(Alert, String)> {
+        let mut result = Vec::new();
+        for (key, escalation) in self.escalations.iter_mut() {
+            if escalation.acknowledged {
+                continue;
+            }
+            let steps = &self.policies[escalation.policy].steps;
+            while let Some(step) = steps.get(escalation.next_step) {
+                if now.duration_since(escalation.started_at) < step.after {
+                    break;
+                }
+                escalation.next_step += 1;
+                info!(
+                    kind = escalation.alert.kind.as_str(),
+                    "escalating {} to {} (step {}/{})",
+                    key,
+                    step.receiver,
+                    escalation.next_step,
+                    steps.len()
+                );
+                let mut alert = escalation.alert.clone();
+                alert.field(
+                    "escalation",
+                    format!("{}/{}", escalation.next_step, steps.len()),
+                );
+                result.push((alert, step.receiver.clone()));
+            }
+        }
+        let policies = &self.policies;
+        self.escalations.retain(|_, escalation| {
+            escalation.alert.kind.is_stateful()
+                || escalation.next_step < policies[escalation.policy].steps.len()
+        });
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::AlertKind;
+
+    fn escalator() -> Escalator {
+        let mut matchers = BTreeMap::new();
+        matchers.insert("severity".to_owned(), "critical".to_owned());
+        let mut escalator = Escalator::new();
+        escalator.add_policy(EscalationPolicy {
+            matchers,
+            steps: vec![
+                EscalationStep {
+                    after: Duration::from_secs(600),
+                    receiver: "secondary".to_owned(),
+                },
+                EscalationStep {
+                    after: Duration::from_secs(1_800),
+                    receiver: "manager".to_owned(),
+                },
+            ],
+        });
+        escalator
+    }
+
+    fn receivers(due: Vec<(Alert, String)>) -> Vec<String> {
+        due.into_iter().map(|(_, receiver)| receiver).collect()
+    }
+
+    #[test]
+    fn test_escalate_until_resolved() {
+        let mut escalator = escalator();
+        let start = Instant::now();
+        let alert = Alert::new(
+            AlertKind::ValidatorStatus,
+            "http://127.0.0.1:9090",
+            "jailed".to_owned(),
+        );
+        assert!(escalator.observe(&alert, start).is_empty());
+        assert!(escalator.due(start + Duration::from_secs(599)).is_empty());
+        let due = escalator.due(start + Duration::from_secs(600));
+        assert_eq!(due[0].0.fields["escalation"], "1/2");
+        assert_eq!(receivers(due), vec!["secondary"]);
+        assert!(escalator.due(start + Duration::from_secs(601)).is_empty());
+
+        let resolved = Alert::resolved(
+            AlertKind::ValidatorStatus,
+            "http://127.0.0.1:9090",
+            "healthy".to_owned(),
+        );
+        assert_eq!(escalator.observe(&resolved, start), vec!["secondary"]);
+        assert!(escalator.due(start + Duration::from_secs(3_600)).is_empty());
+    }
+
+    #[test]
+    fn test_acknowledge() {
+        let mut escalator = escalator();
+        let start = Instant::now();
+        let alert = Alert::new(
+            AlertKind::Slashes,
+            "http://127.0.0.1:9090",
+            "slashed".to_owned(),
+        );
+        escalator.observe(&alert, start);
+        // a late check escalates every step passed at once.
+        assert_eq!(
+            receivers(escalator.due(start + Duration::from_secs(1_800))),
+            vec!["secondary", "manager"]
+        );
+        assert!(!escalator.acknowledge(alert.dedup_key().as_str()));
+
+        escalator.observe(&alert, start);
+        assert_eq!(escalator.events().count(), 1);
+        assert!(escalator.acknowledge(alert.dedup_key().as_str()));
+        assert!(escalator.due(start + Duration::from_secs(1_800)).is_empty());
+        assert_eq!(escalator.events().count(), 0);
+
+        // not matching any policy.
+        let mut warning = alert.clone();
+        warning.severity = crate::Severity::Warning;
+        escalator.observe(&warning, start);
+        assert!(escalator.due(start + Duration::from_secs(1_800)).is_empty());
+    }
+}
@@ -0,0 +1,362 @@
+use crate::{Alert, Result};
+use anyhow::Context;
+use chrono::{DateTime, TimeZone, Utc};
+use logger::prelude::*;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, params_from_iter, Connection};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS alerts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    status TEXT NOT NULL,
+    endpoint TEXT NOT NULL,
+    validator TEXT,
+    height INTEGER,
+    message TEXT NOT NULL,
+    labels TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    outcome TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS alerts_timestamp ON alerts (timestamp);
+";
+
+/// what the dispatcher did with an alert.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Delivered,
+    Inhibited,
+    Silenced,
+    RateLimited,
+    Escalated,
+}
+
+impl Outcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Delivered => "delivered",
+            Self::Inhibited => "inhibited",
+            Self::Silenced => "silenced",
+            Self::RateLimited => "rate_limited",
+            Self::Escalated => "escalated",
+        }
+    }
+}
+
+/// a stored alert.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryRecord {
+    pub id: i64,
+    pub timestamp: DateTime<Utc>,
+    pub kind: String,
+    pub severity: String,
+    pub status: String,
+    pub endpoint: String,
+    pub validator: Option<String>,
+    pub height: Option<i64>,
+    pub message: String,
+    pub labels: BTreeMap<String, String>,
+    /// the whole alert as json.
+    pub payload: Value,
+    pub outcome: String,
+}
+
+impl HistoryRecord {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "timestamp": self.timestamp.to_rfc3339(),
+            "kind": self.kind,
+            "severity": self.severity,
+            "status": self.status,
+            "endpoint": self.endpoint,
+            "validator": self.validator,
+            "height": self.height,
+            "message": self.message,
+            "labels": self.labels,
+            "payload": self.payload,
+            "outcome": self.outcome,
+        })
+    }
+}
+
+/// conditions to filter the stored alerts with. (every condition is optional)
+#[derive(Clone, Debug, Default)]
+pub struct HistoryQuery {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub validator: Option<String>,
+    pub endpoint: Option<String>,
+    pub kinds: Vec<String>,
+    /// the latest ones are returned if more alerts match.
+    pub limit: Option<u32>,
+}
+
+/// every alert and state transition the dispatcher handled, stored in a sqlite database for incident reviews.
+pub struct AlertHistory {
+    conn: Mutex<Connection>,
+}
+
+impl AlertHistory {
+    pub fn open(path: &str) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("failed to open history database: {}", path))?;
+        conn.execute_batch(SCHEMA)
+            .with_context(|| format!("failed to initialize history database: {}", path))?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn record(&self, alert: &Alert, outcome: Outcome) -> Result<()> {
+        self.conn.lock().unwrap().execute(
+            "INSERT INTO alerts (timestamp, kind, severity, status, endpoint, validator, height, message, labels, payload, outcome)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                alert.timestamp.timestamp_millis(),
+                alert.kind.as_str(),
+                alert.severity.as_str(),
+                alert.status.as_str(),
+                alert.endpoint,
+                alert.validator,
+                alert.height,
+                alert.message,
+                serde_json::to_string(&alert.labels)?,
+                alert.to_json().to_string(),
+                outcome.as_str(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// the matching alerts in chronological order.
+    pub fn query(&self, query: &HistoryQuery) -> Result<Vec<HistoryRecord>> {
+        let mut conditions = Vec::new();
+        let mut values: Vec<SqlValue> = Vec::new();
+        if let Some(since) = query.since {
+            conditions.push("timestamp >= ?".to_owned());
+            values.push(since.timestamp_millis().into());
+        }
+        if let Some(until) = query.until {
+            conditions.push("timestamp < ?".to_owned());
+            values.push(until.timestamp_millis().into());
+        }
+        if let Some(validator) = query.validator.as_ref() {
+            conditions.push("validator = ?".to_owned());
+            values.push(validator.clone().into());
+        }
+        if let Some(endpoint) = query.endpoint.as_ref() {
+            conditions.push("endpoint = ?".to_owned());
+            values.push(endpoint.clone().into());
+        }
+        if !query.kinds.is_empty() {
+            conditions.push(format!(
+                "kind IN ({})",
+                vec!["?"; query.kinds.len()].join(", ")
+            ));
+            values.extend(query.kinds.iter().map(|kind| kind.clone().into()));
+        }
+        let mut sql = "SELECT id, timestamp, kind, severity, status, endpoint, validator, height, message, labels, payload, outcome FROM alerts".to_owned();
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(conditions.join(" AND ").as_str());
+        }
+        sql.push_str(" ORDER BY timestamp DESC, id DESC");
+        if let Some(limit) = query.limit {
+            sql.push_str(format!(" LIMIT {}", limit).as_str());
+        }
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(sql.as_str())?;
+        let rows = stmt.query_map(params_from_iter(values), |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, String>(5)?,
+                row.get::<_, Option<String>>(6)?,
+                row.get::<_, Option<i64>>(7)?,
+                row.get::<_, String>(8)?,
+                row.get::<_, String>(9)?,
+                row.get::<_, String>(10)?,
+                row.get::<_, String>(11)?,
+            ))
+        })?;
+        let mut result = Vec::new();
+        for row in rows {
+            let (
+                id,
+                timestamp,
+                kind,
+                severity,
+                status,
+                endpoint,
+                validator,
+                height,
+                message,
+                labels,
+                payload,
+                outcome,
+            ) = row?;
+            result.push(HistoryRecord {
+                id,
+                timestamp: Utc.timestamp_millis_opt(timestamp).unwrap(),
+                kind,
+                severity,
+                status,
+                endpoint,
+                validator,
+                height,
+                message,
+                labels: serde_json::from_str(labels.as_str())?,
+                payload: serde_json::from_str(payload.as_str())?,
+                outcome,
+            });
+        }
+        result.reverse();
+        Ok(result)
+    }
+}
+
+/// stores alerts into the history in a dedicated thread, not to block the dispatcher with disk writes.
+pub struct HistoryWriter {
+    sender: UnboundedSender<(Alert, Outcome)>,
+    handle: JoinHandle<AlertHistory>,
+}
+
+impl HistoryWriter {
+    pub fn spawn(history: AlertHistory) -> Result<Self> {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<(Alert, Outcome)>();
+        let handle = thread::Builder::new()
+            .name("alert-history".to_owned())
+            .spawn(move || {
+                while let Some((alert, outcome)) = receiver.blocking_recv() {
+                    if let Err(err) = history.record(&alert, outcome) {
+                        error!(
+                            "failed to store {} alert in history: {}",
+                            alert.kind.as_str(),
+                            err
+                        );
+                    }
+                }
+                history
+            })?;
+        Ok(Self { sender, handle })
+    }
+
+    /// queues the alert to store.
+    pub fn record(&self, alert: &Alert, outcome: Outcome) {
+        if self.sender.send((alert.clone(), outcome)).is_err() {
+            error!(
+                "history writer has stopped, {} alert is not stored",
+                alert.kind.as_str()
+            );
+        }
+    }
+
+    /// waits until the queued alerts are stored.
+    pub fn close(self) -> AlertHistory {
+        drop(self.sender);
+        self.handle
+            .join()
+            .expect("history writer must not panic...")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::AlertKind;
+    use chrono::Duration;
+
+    #[test]
+    fn test_record_and_query() {
+        let history = AlertHistory::open(":memory:").unwrap();
+        let now = Utc
+            .timestamp_millis_opt(Utc::now().timestamp_millis())
+            .unwrap();
+        let mut missed = Alert::new(
+            AlertKind::MissedBlock,
+            "http://127.0.0.1:9090",
+            "missed".to_owned(),
+        );
+        missed.timestamp = now - Duration::hours(2);
+        missed.validator("torivaloper1xxx").height(100);
+        missed.labels.insert("team".to_owned(), "core".to_owned());
+        history.record(&missed, Outcome::Delivered).unwrap();
+        let mut syncing = Alert::new(
+            AlertKind::IsSyncing,
+            "http://127.0.0.1:9091",
+            "syncing".to_owned(),
+        );
+        syncing.timestamp = now - Duration::hours(1);
+        history.record(&syncing, Outcome::Silenced).unwrap();
+        let mut resolved = missed.clone();
+        resolved.status = crate::AlertStatus::Resolved;
+        resolved.timestamp = now;
+        history.record(&resolved, Outcome::Delivered).unwrap();
+
+        let all = history.query(&HistoryQuery::default()).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].timestamp, missed.timestamp);
+        assert_eq!(all[0].height, Some(100));
+        assert_eq!(all[0].labels.get("team").unwrap(), "core");
+        assert_eq!(all[0].payload["id"], missed.id());
+        assert_eq!(all[1].outcome, "silenced");
+        assert_eq!(all[2].status, "resolved");
+
+        let records = history
+            .query(&HistoryQuery {
+                validator: Some("torivaloper1xxx".to_owned()),
+                since: Some(now - Duration::minutes(90)),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].timestamp, now);
+
+        let records = history
+            .query(&HistoryQuery {
+                kinds: vec!["is_syncing".to_owned(), "slashes".to_owned()],
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].endpoint, "http://127.0.0.1:9091");
+
+        // the latest ones within the limit.
+        let records = history
+            .query(&HistoryQuery {
+                limit: Some(2),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].kind, "is_syncing");
+    }
+
+    #[test]
+    fn test_writer() {
+        let writer = HistoryWriter::spawn(AlertHistory::open(":memory:").unwrap()).unwrap();
+        for height in 0..10 {
+            let mut alert = Alert::new(
+                AlertKind::MissedBlock,
+                "http://127.0.0.1:9090",
+                "missed".to_owned(),
+            );
+            alert.height(height);
+            writer.record(&alert, Outcome::Delivered);
+        }
+        let records = writer.close().query(&HistoryQuery::default()).unwrap();
+        assert_eq!(records.len(), 10);
+        assert_eq!(records[9].height, Some(9));
+    }
+}
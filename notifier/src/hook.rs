@@ -0,0 +1,376 @@
+use crate::{Alert, Result};
+use anyhow::anyhow;
+use config::configs::HookConfig;
+use logger::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::process::{ExitStatus, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+
+/// how much of stdout/stderr of the command to log.
+const MAX_OUTPUT_LENGTH: usize = 1_024;
+
+/// a local executable to run as the first aid for alerts matching the labels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hook {
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub matchers: BTreeMap<String, String>,
+    /// how long the alert must have been firing before running the command.
+    pub after: Duration,
+    /// how long to wait before running the command again for the same condition.
+    pub cooldown: Duration,
+    pub timeout: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HookOutput {
+    pub status: ExitStatus,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl Hook {
+    pub fn from_config(config: &HookConfig) -> Self {
+        Self {
+            name: config.name.clone(),
+            command: config.command.clone(),
+            args: config.args.clone(),
+            matchers: config.matchers.clone(),
+            after: config.get_after(),
+            cooldown: config.get_cooldown(),
+            timeout: config.get_timeout(),
+        }
+    }
+
+    fn matches(&self, alert: &Alert) -> bool {
+        let labels = alert.all_labels();
+        self.matchers
+            .iter()
+            .all(|(key, value)| labels.get(key) == Some(value))
+    }
+
+    /// the alert as `TERITORIMON_*` environment variables.
+    /// (fields and labels as `TERITORIMON_FIELD_<NAME>` and `TERITORIMON_LABEL_<NAME>`)
+    fn envs(&self, alert: &Alert) -> Vec<(String, String)> {
+        let mut result = vec![
+            ("TERITORIMON_HOOK".to_owned(), self.name.clone()),
+            ("TERITORIMON_ALERT_ID".to_owned(), alert.id()),
+            (
+                "TERITORIMON_KIND".to_owned(),
+                alert.kind.as_str().to_owned(),
+            ),
+            (
+                "TERITORIMON_SEVERITY".to_owned(),
+                alert.severity.as_str().to_owned(),
+            ),
+            (
+                "TERITORIMON_STATUS".to_owned(),
+                alert.status.as_str().to_owned(),
+            ),
+            ("TERITORIMON_ENDPOINT".to_owned(), alert.endpoint.clone()),
+            (
+                "TERITORIMON_VALIDATOR".to_owned(),
+                alert.validator.clone().unwrap_or_default(),
+            ),
+            (
+                "TERITORIMON_HEIGHT".to_owned(),
+                alert
+                    .height
+                    .map(|height| height.to_string())
+                    .unwrap_or_default(),
+            ),
+            ("TERITORIMON_MESSAGE".to_owned(), alert.message.clone()),
+            (
+                "TERITORIMON_TIMESTAMP".to_owned(),
+                alert.timestamp.to_rfc3339(),
+            ),
+            (
+                "TERITORIMON_STARTED_AT".to_owned(),
+                alert
+                    .started_at
+                    .map(|started_at| started_at.to_rfc3339())
+                    .unwrap_or_default(),
+            ),
+        ];
+        for (key, value) in &alert.fields {
+            result.push((
+                format!("TERITORIMON_FIELD_{}", env_name(key)),
+                value.clone(),
+            ));
+        }
+        for (key, value) in &alert.labels {
+            result.push((
+                format!("TERITORIMON_LABEL_{}", env_name(key)),
+                value.clone(),
+            ));
+        }
+        result
+    }
+
+    /// runs the command with the alert as json on stdin, and kills it on timeout.
+    pub async fn run(&self, alert: &Alert) -> Result<HookOutput> {
+        let mut child = Command::new(self.command.as_str())
+            .args(&self.args)
+            .envs(self.envs(alert))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+        let mut stdin = child.stdin.take().expect("stdin must be piped");
+        let payload = alert.to_json().to_string();
+        let output = tokio::time::timeout(self.timeout, async move {
+            // the command does not have to read stdin.
+            let _ = stdin.write_all(payload.as_bytes()).await;
+            drop(stdin);
+            child.wait_with_output().await
+        })
+        .await
+        .map_err(|_| anyhow!("timed out after {:?}", self.timeout))??;
+        Ok(HookOutput {
+            status: output.status,
+            stdout: truncate(String::from_utf8_lossy(&output.stdout).trim()),
+            stderr: truncate(String::from_utf8_lossy(&output.stderr).trim()),
+        })
+    }
+}
+
+fn env_name(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn truncate(output: &str) -> String {
+    match output.char_indices().nth(MAX_OUTPUT_LENGTH) {
+        Some((index, _)) => format!("{}...", &output[..index]),
+        None => output.to_owned(),
+    }
+}
+
+#[derive(Debug)]
+struct Firing {
+    alert: Alert,
+    started_at: Instant,
+}
+
+/// runs the matching hooks of delivered alerts in the background,
+/// at most `concurrency` commands at once.
+///
+/// conditions (e.g. is_syncing) run the hooks again every cooldown until resolved,
+/// and one-shot events (e.g. slashes) run them once.
+#[derive(Debug)]
+pub struct HookRunner {
+    hooks: Vec<Arc<Hook>>,
+    /// firing alerts matching any hook by dedup key.
+    firing: HashMap<String, Firing>,
+    /// when the hooks have run last by hook index and dedup key.
+    last_runs: HashMap<(usize, String), Instant>,
+    semaphore: Arc<Semaphore>,
+}
+
+impl HookRunner {
+    pub fn new(concurrency: usize) -> Self {
+        Self {
+            hooks: Vec::new(),
+            firing: HashMap::new(),
+            last_runs: HashMap::new(),
+            semaphore: Arc::new(Semaphore::new(concurrency)),
+        }
+    }
+
+    pub fn add_hook(&mut self, hook: Hook) -> &mut Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// starts waiting for the hooks of a firing alert, or stops running them if resolved.
+    pub fn observe(&mut self, alert: &Alert, now: Instant) {
+        let key = alert.dedup_key();
+        if alert.is_resolved() {
+            self.firing.remove(&key);
+            return;
+        }
+        if let Some(firing) = self.firing.get_mut(&key) {
+            firing.alert = alert.clone();
+            return;
+        }
+        if self.hooks.iter().any(|hook| hook.matches(alert)) {
+            self.firing.insert(
+                key,
+                Firing {
+                    alert: alert.clone(),
+                    started_at: now,
+                },
+            );
+        }
+    }
+
+    /// the hooks (by index) to run now with the dedup keys of the alerts.
+    fn due(&self, now: Instant) -> Vec<(usize, String)> {
+        let mut result = Vec::new();
+        for (key, firing) in &self.firing {
+            for (index, hook) in self.hooks.iter().enumerate() {
+                if !hook.matches(&firing.alert)
+                    || now.duration_since(firing.started_at) < hook.after
+                {
+                    continue;
+                }
+                let cooling_down = self
+                    .last_runs
+                    .get(&(index, key.clone()))
+                    .map(|last_run| now.duration_since(*last_run) < hook.cooldown)
+                    .unwrap_or_default();
+                if !cooling_down {
+                    result.push((index, key.clone()));
+                }
+            }
+        }
+        result
+    }
+
+    /// runs the hooks due in the background. the ones exceeding the concurrency are tried again later.
+    pub fn run_due(&mut self, now: Instant) {
+        let mut deferred = HashSet::new();
+        for (index, key) in self.due(now) {
+            let permit = match self.semaphore.clone().try_acquire_owned() {
+                Ok(permit) => permit,
+                Err(_) => {
+                    info!(
+                        "hook {} for {} is deferred since too many hooks are running",
+                        self.hooks[index].name, key
+                    );
+                    deferred.insert(key);
+                    continue;
+                }
+            };
+            self.last_runs.insert((index, key.clone()), now);
+            let hook = self.hooks[index].clone();
+            let alert = self.firing[&key].alert.clone();
+            info!(
+                kind = alert.kind.as_str(),
+                "running hook {} for {}", hook.name, key
+            );
+            tokio::spawn(async move {
+                let _permit = permit;
+                match hook.run(&alert).await {
+                    Ok(output) if output.status.success() => info!(
+                        kind = alert.kind.as_str(),
+                        "hook {} for {} exited with {}, stdout: {}, stderr: {}",
+                        hook.name,
+                        key,
+                        output.status,
+                        output.stdout,
+                        output.stderr
+                    ),
+                    Ok(output) => error!(
+                        "hook {} for {} exited with {}, stdout: {}, stderr: {}",
+                        hook.name, key, output.status, output.stdout, output.stderr
+                    ),
+                    Err(err) => error!("failed to run hook {} for {}: {}", hook.name, key, err),
+                }
+            });
+        }
+        // one-shot events are done once every matching hook has run. (or been cooling down)
+        let hooks = &self.hooks;
+        self.firing.retain(|key, firing| {
+            firing.alert.kind.is_stateful()
+                || deferred.contains(key)
+                || hooks.iter().any(|hook| {
+                    hook.matches(&firing.alert)
+                        && now.duration_since(firing.started_at) < hook.after
+                })
+        });
+        self.last_runs
+            .retain(|(index, _), last_run| now.duration_since(*last_run) < hooks[*index].cooldown);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::AlertKind;
+
+    const ENDPOINT: &str = "http://127.0.0.1:9090";
+
+    fn hook(command: &str, args: &[&str]) -> Hook {
+        let mut matchers = BTreeMap::new();
+        matchers.insert("kind".to_owned(), "is_syncing".to_owned());
+        Hook {
+            name: "restart".to_owned(),
+            command: command.to_owned(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            matchers,
+            after: Duration::from_secs(600),
+            cooldown: Duration::from_secs(1_800),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn test_due() {
+        let mut runner = HookRunner::new(1);
+        runner.add_hook(hook("true", &[]));
+        let now = Instant::now();
+        let syncing = Alert::new(AlertKind::IsSyncing, ENDPOINT, "syncing".to_owned());
+        runner.observe(&syncing, now);
+        runner.observe(
+            &Alert::new(AlertKind::MissedBlock, ENDPOINT, "missed".to_owned()),
+            now,
+        );
+        assert!(runner.due(now + Duration::from_secs(599)).is_empty());
+        let due = runner.due(now + Duration::from_secs(600));
+        assert_eq!(due, vec![(0, syncing.dedup_key())]);
+
+        // cooldown after running.
+        runner
+            .last_runs
+            .insert((0, syncing.dedup_key()), now + Duration::from_secs(600));
+        assert!(runner.due(now + Duration::from_secs(2_399)).is_empty());
+        assert_eq!(runner.due(now + Duration::from_secs(2_400)).len(), 1);
+
+        runner.observe(
+            &Alert::resolved(AlertKind::IsSyncing, ENDPOINT, "synced".to_owned()),
+            now + Duration::from_secs(2_400),
+        );
+        assert!(runner.due(now + Duration::from_secs(2_400)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run() {
+        let mut alert = Alert::new(AlertKind::IsSyncing, ENDPOINT, "syncing".to_owned());
+        alert
+            .labels
+            .insert("team-name".to_owned(), "core".to_owned());
+        let output = hook(
+            "sh",
+            &[
+                "-c",
+                "cat; echo \" $TERITORIMON_KIND $TERITORIMON_LABEL_TEAM_NAME\"",
+            ],
+        )
+        .run(&alert)
+        .await
+        .unwrap();
+        assert!(output.status.success());
+        assert_eq!(
+            output.stdout,
+            format!("{} is_syncing core", alert.to_json())
+        );
+
+        let mut sleeping = hook("sleep", &["10"]);
+        sleeping.timeout = Duration::from_millis(100);
+        assert!(sleeping.run(&alert).await.is_err());
+    }
+}
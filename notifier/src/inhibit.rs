@@ -0,0 +1,201 @@
+use crate::Alert;
+use config::configs::InhibitRuleConfig;
+use std::collections::{BTreeMap, HashMap};
+
+/// mutes alerts matching `target_match` while an alert matching `source_match` is firing,
+/// if both have the same values of the `equal` labels. (e.g. no missed blocks while syncing)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InhibitRule {
+    pub source_match: BTreeMap<String, String>,
+    pub target_match: BTreeMap<String, String>,
+    pub equal: Vec<String>,
+}
+
+fn matches(matchers: &BTreeMap<String, String>, labels: &BTreeMap<String, String>) -> bool {
+    matchers
+        .iter()
+        .all(|(key, value)| labels.get(key) == Some(value))
+}
+
+impl InhibitRule {
+    pub fn from_config(config: &InhibitRuleConfig) -> Self {
+        Self {
+            source_match: config.source_match.clone(),
+            target_match: config.target_match.clone(),
+            equal: config.equal.clone(),
+        }
+    }
+
+    fn inhibits(
+        &self,
+        source: &BTreeMap<String, String>,
+        target: &BTreeMap<String, String>,
+    ) -> bool {
+        matches(&self.target_match, target)
+            && matches(&self.source_match, source)
+            && self
+                .equal
+                .iter()
+                .all(|key| source.get(key) == target.get(key))
+    }
+}
+
+/// applies inhibit rules against the firing alerts.
+#[derive(Debug, Default)]
+pub struct Inhibitor {
+    rules: Vec<InhibitRule>,
+    /// dedup keys of the alerts whose firing notification was inhibited to the dedup key
+    /// of the source, so that their resolution is not notified either.
+    inhibited: HashMap<String, String>,
+}
+
+impl Inhibitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_rule(&mut self, rule: InhibitRule) -> &mut Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// returns the dedup key of the firing alert which inhibits the alert.
+    pub fn inhibited_by<'a, I>(&mut self, alert: &Alert, firing: I) -> Option<String>
+    where
+        I: IntoIterator<Item = &'a Alert>,
+    {
+        let key = alert.dedup_key();
+        if alert.is_resolved() {
+            return if self.inhibited.remove(&key).is_some() {
+                Some("the inhibited firing alert".to_owned())
+            } else {
+                None
+            };
+        }
+        if self.rules.is_empty() {
+            return None;
+        }
+        let labels = alert.all_labels();
+        let source = firing.into_iter().find(|source| {
+            if source.dedup_key() == key {
+                return false;
+            }
+            let source_labels = source.all_labels();
+            self.rules
+                .iter()
+                .any(|rule| rule.inhibits(&source_labels, &labels))
+        });
+        match source {
+            Some(source) => {
+                let source_key = source.dedup_key();
+                if alert.kind.is_stateful() {
+                    self.inhibited.insert(key, source_key.clone());
+                }
+                Some(source_key)
+            }
+            None => {
+                self.inhibited.remove(&key);
+                None
+            }
+        }
+    }
+
+    /// forgets the alerts inhibited by the resolved source, and returns their dedup keys
+    /// to notify of them if they are still firing.
+    pub fn released_by(&mut self, source_key: &str) -> Vec<String> {
+        let result: Vec<String> = self
+            .inhibited
+            .iter()
+            .filter(|(_, source)| source.as_str() == source_key)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &result {
+            self.inhibited.remove(key);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::AlertKind;
+
+    fn syncing_rule() -> InhibitRule {
+        InhibitRule {
+            source_match: vec![("kind".to_owned(), "is_syncing".to_owned())]
+                .into_iter()
+                .collect(),
+            target_match: vec![("kind".to_owned(), "missed_block".to_owned())]
+                .into_iter()
+                .collect(),
+            equal: vec!["endpoint".to_owned()],
+        }
+    }
+
+    fn missed_block(endpoint: &str, resolved: bool) -> Alert {
+        let mut alert = if resolved {
+            Alert::resolved(AlertKind::MissedBlock, endpoint, "signed".to_owned())
+        } else {
+            Alert::new(AlertKind::MissedBlock, endpoint, "missed".to_owned())
+        };
+        alert.validator("A");
+        alert
+    }
+
+    #[test]
+    fn test_inhibited_by() {
+        let mut inhibitor = Inhibitor::new();
+        inhibitor.add_rule(syncing_rule());
+        let syncing = Alert::new(
+            AlertKind::IsSyncing,
+            "http://10.0.0.1:9090",
+            "syncing".to_owned(),
+        );
+        let firing = vec![syncing.clone()];
+
+        assert_eq!(
+            inhibitor.inhibited_by(&missed_block("http://10.0.0.1:9090", false), &firing),
+            Some(syncing.dedup_key())
+        );
+        // another endpoint is not inhibited.
+        assert_eq!(
+            inhibitor.inhibited_by(&missed_block("http://10.0.0.2:9090", false), &firing),
+            None
+        );
+        // the source itself is not inhibited.
+        assert_eq!(inhibitor.inhibited_by(&syncing, &firing), None);
+        // the resolution of the inhibited alert is not notified either.
+        assert!(inhibitor
+            .inhibited_by(&missed_block("http://10.0.0.1:9090", true), &[])
+            .is_some());
+        assert!(inhibitor
+            .inhibited_by(&missed_block("http://10.0.0.2:9090", true), &[])
+            .is_none());
+    }
+
+    #[test]
+    fn test_released_by() {
+        let mut inhibitor = Inhibitor::new();
+        inhibitor.add_rule(syncing_rule());
+        let syncing = Alert::new(
+            AlertKind::IsSyncing,
+            "http://10.0.0.1:9090",
+            "syncing".to_owned(),
+        );
+        let firing = vec![syncing.clone()];
+        let missed = missed_block("http://10.0.0.1:9090", false);
+        assert!(inhibitor.inhibited_by(&missed, &firing).is_some());
+        assert!(inhibitor.released_by("other").is_empty());
+        // the target still firing is notified of once the source resolves.
+        assert_eq!(
+            inhibitor.released_by(syncing.dedup_key().as_str()),
+            vec![missed.dedup_key()]
+        );
+        assert!(inhibitor.inhibited_by(&missed, &[]).is_none());
+        // and so is its resolution.
+        assert!(inhibitor
+            .inhibited_by(&missed_block("http://10.0.0.1:9090", true), &[])
+            .is_none());
+    }
+}
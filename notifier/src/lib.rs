@@ -0,0 +1,162 @@
+pub mod active;
+mod alert;
+pub mod digest;
+pub mod discord;
+pub mod email;
+pub mod escalation;
+pub mod history;
+pub mod hook;
+pub mod inhibit;
+pub mod lifecycle;
+pub mod log;
+pub mod matrix;
+pub mod opsgenie;
+pub mod pagerduty;
+pub mod queue;
+pub mod route;
+pub mod silence;
+pub mod slack;
+pub mod status;
+pub mod telegram;
+pub mod template;
+#[cfg(test)]
+mod testing;
+pub mod throttle;
+pub mod webhook;
+
+pub use alert::*;
+pub type Result<T> = anyhow::Result<T>;
+
+use async_trait::async_trait;
+use config::configs::NotifierConfig;
+use futures::SinkExt;
+use logger::prelude::*;
+use std::collections::BTreeMap;
+use std::sync::mpsc::SyncSender;
+use std::time::Duration;
+
+/// how long to wait for a response of the chat / paging services.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+const HTTP_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// the http client notifiers send requests with, so that unresponsive services do not stall deliveries.
+pub(crate) fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(HTTP_TIMEOUT)
+        .connect_timeout(HTTP_CONNECT_TIMEOUT)
+        .build()
+        .expect("http client must be built...")
+}
+
+/// a sink which delivers alerts somewhere (logger, chat services, paging services...)
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    fn name(&self) -> &str;
+    async fn notify(&self, alert: &Alert) -> Result<()>;
+}
+
+pub fn new_notifier(config: &NotifierConfig) -> Result<Box<dyn Notifier>> {
+    Ok(match config {
+        NotifierConfig::Slack(config) => Box::new(slack::SlackNotifier::new(config.clone())),
+        NotifierConfig::Discord(config) => Box::new(discord::DiscordNotifier::new(config.clone())),
+        NotifierConfig::Telegram(config) => {
+            Box::new(telegram::TelegramNotifier::new(config.clone()))
+        }
+        NotifierConfig::Pagerduty(config) => {
+            Box::new(pagerduty::PagerDutyNotifier::new(config.clone()))
+        }
+        NotifierConfig::Opsgenie(config) => {
+            Box::new(opsgenie::OpsgenieNotifier::new(config.clone()))
+        }
+        NotifierConfig::Webhook(config) => Box::new(webhook::WebhookNotifier::new(config.clone())?),
+        NotifierConfig::Email(config) => Box::new(email::EmailNotifier::new(config.clone())?),
+        NotifierConfig::Matrix(config) => Box::new(matrix::MatrixNotifier::new(config.clone())),
+    })
+}
+
+#[derive(Debug)]
+pub enum AlertMessage {
+    Alert(Alert),
+    /// whether the validator signed a block, which digests count.
+    Signature {
+        endpoint: String,
+        signed: bool,
+    },
+    /// stops reminding of and escalating the active alert of the id.
+    Acknowledge(String),
+    Terminate(SyncSender<()>),
+}
+
+impl From<Alert> for AlertMessage {
+    fn from(inner: Alert) -> Self {
+        Self::Alert(inner)
+    }
+}
+
+/// the handle checkers emit alerts into.
+#[derive(Debug, Clone)]
+pub struct AlertSender {
+    inner: channel::Sender<AlertMessage>,
+    labels: BTreeMap<String, String>,
+}
+
+impl AlertSender {
+    pub fn new(inner: channel::Sender<AlertMessage>) -> Self {
+        Self {
+            inner,
+            labels: BTreeMap::new(),
+        }
+    }
+
+    /// attaches the labels to every alert emitted through this sender.
+    pub fn labels(&mut self, labels: BTreeMap<String, String>) -> &mut Self {
+        self.labels = labels;
+        self
+    }
+
+    pub fn emit(&mut self, mut alert: Alert) {
+        for (key, value) in &self.labels {
+            alert
+                .labels
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        if let Err(err) = self.inner.try_send(alert.into()) {
+            // the dispatcher is gone or congested, so at least leave the log line.
+            error!("failed to emit alert: {}", err);
+        }
+    }
+
+    pub fn report_signature(&mut self, endpoint: &str, signed: bool) {
+        let message = AlertMessage::Signature {
+            endpoint: endpoint.to_owned(),
+            signed,
+        };
+        if let Err(err) = self.inner.try_send(message) {
+            error!("failed to report signature: {}", err);
+        }
+    }
+
+    pub fn acknowledge(&mut self, id: &str) {
+        if let Err(err) = self
+            .inner
+            .try_send(AlertMessage::Acknowledge(id.to_owned()))
+        {
+            error!("failed to acknowledge alert: {}", err);
+        }
+    }
+
+    /// waits until the dispatcher has delivered and stored the alerts queued.
+    pub fn terminate(&mut self) {
+        let (oneshot_sender, oneshot_receiver) = std::sync::mpsc::sync_channel(1);
+        // waits for room in the queue, which is likely to be full when the dispatcher is congested.
+        let message = AlertMessage::Terminate(oneshot_sender);
+        if let Err(err) = futures::executor::block_on(self.inner.send(message)) {
+            error!("failed to terminate alert dispatcher: {}", err);
+            return;
+        }
+        if oneshot_receiver.recv().is_err() {
+            error!("alert dispatcher has stopped before terminating");
+        }
+    }
+}
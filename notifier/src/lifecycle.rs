@@ -0,0 +1,323 @@
+use crate::{format_duration, Alert, AlertKind, AlertStatus};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// tracks firing conditions, keyed by checker + validator + endpoint, so that only
+/// the transitions (firing -> resolved and vice versa) are delivered.
+///
+/// checkers report the current condition on every check, and this turns them into
+/// one `FIRING` alert when the condition starts and one `RESOLVED` alert with the
+/// duration when it clears. one-shot events (e.g. new proposals) pass through as is.
+///
+/// with a repeat interval, firing conditions are notified again every interval until
+/// acknowledged, and identical one-shot events are not notified again within the interval.
+#[derive(Debug, Default)]
+pub struct AlertLifecycle {
+    repeat_intervals: HashMap<AlertKind, Duration>,
+    firing: HashMap<String, Firing>,
+    /// when the identical one-shot events were notified last.
+    notified_events: HashMap<String, DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+struct Firing {
+    alert: Alert,
+    /// `None` to notify again on the next observation.
+    notified_at: Option<DateTime<Utc>>,
+    acknowledged: bool,
+}
+
+impl AlertLifecycle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn repeat_interval(&mut self, kind: AlertKind, repeat_interval: Duration) -> &mut Self {
+        self.repeat_intervals.insert(kind, repeat_interval);
+        self
+    }
+
+    fn is_due(&self, kind: AlertKind, notified_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        match self.repeat_intervals.get(&kind) {
+            Some(repeat_interval) => elapsed(notified_at, now) >= *repeat_interval,
+            None => false,
+        }
+    }
+
+    /// returns the alert to deliver if the observation changed the state
+    /// or it is time to remind.
+    pub fn observe(&mut self, alert: Alert) -> Option<Alert> {
+        if !alert.kind.is_stateful() {
+            return self.observe_event(alert);
+        }
+        let key = alert.dedup_key();
+        match alert.status {
+            AlertStatus::Firing => self.observe_firing(key, alert),
+            AlertStatus::Resolved => {
+                let fired = self.firing.remove(&key)?;
+                let mut alert = alert;
+                alert.started_at = fired.alert.started_at;
+                if let Some(duration) = alert.duration() {
+                    alert.field("duration", format_duration(duration));
+                }
+                Some(alert)
+            }
+        }
+    }
+
+    fn observe_firing(&mut self, key: String, mut alert: Alert) -> Option<Alert> {
+        let now = alert.timestamp;
+        let (notified_at, acknowledged) = match self.firing.get(&key) {
+            Some(current) => {
+                alert.started_at = current.alert.started_at;
+                (current.notified_at, current.acknowledged)
+            }
+            None => {
+                alert.started_at = Some(now);
+                self.firing.insert(
+                    key,
+                    Firing {
+                        alert: alert.clone(),
+                        notified_at: Some(now),
+                        acknowledged: false,
+                    },
+                );
+                return Some(alert);
+            }
+        };
+        let due = !acknowledged
+            && notified_at
+                .map(|notified_at| self.is_due(alert.kind, notified_at, now))
+                .unwrap_or(true);
+        if due {
+            if let Some(duration) = alert.duration() {
+                alert.field("duration", format_duration(duration));
+            }
+        }
+        // keep the latest details even if not to notify again.
+        self.firing.insert(
+            key,
+            Firing {
+                alert: alert.clone(),
+                notified_at: if due { Some(now) } else { notified_at },
+                acknowledged,
+            },
+        );
+        if due {
+            Some(alert)
+        } else {
+            None
+        }
+    }
+
+    fn observe_event(&mut self, alert: Alert) -> Option<Alert> {
+        let repeat_interval = match self.repeat_intervals.get(&alert.kind) {
+            Some(repeat_interval) => *repeat_interval,
+            None => return Some(alert),
+        };
+        let now = alert.timestamp;
+        self.notified_events
+            .retain(|_, notified_at| elapsed(*notified_at, now) < repeat_interval);
+        let key = format!("{}/{}", alert.dedup_key(), alert.message);
+        if self.notified_events.contains_key(&key) {
+            return None;
+        }
+        self.notified_events.insert(key, now);
+        Some(alert)
+    }
+
+    /// the alerts currently firing.
+    pub fn firing(&self) -> impl Iterator<Item = &Alert> {
+        self.firing.values().map(|firing| &firing.alert)
+    }
+
+    /// stops reminding of the firing condition until it is resolved.
+    /// returns false if it is not firing.
+    pub fn acknowledge(&mut self, dedup_key: &str) -> bool {
+        match self.firing.get_mut(dedup_key) {
+            Some(firing) => {
+                firing.acknowledged = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// notifies of the firing condition again on the next observation, e.g. once the silence
+    /// which dropped its notification has ended. returns false if it is not firing.
+    pub fn renotify(&mut self, dedup_key: &str) -> bool {
+        match self.firing.get_mut(dedup_key) {
+            Some(firing) => {
+                firing.notified_at = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_acknowledged(&self, dedup_key: &str) -> bool {
+        self.firing
+            .get(dedup_key)
+            .map(|firing| firing.acknowledged)
+            .unwrap_or_default()
+    }
+}
+
+fn elapsed(since: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+    now.signed_duration_since(since)
+        .to_std()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::Duration;
+
+    fn syncing(resolved: bool) -> Alert {
+        let endpoint = "http://127.0.0.1:9090";
+        if resolved {
+            Alert::resolved(AlertKind::IsSyncing, endpoint, "synced".to_owned())
+        } else {
+            Alert::new(AlertKind::IsSyncing, endpoint, "syncing".to_owned())
+        }
+    }
+
+    #[test]
+    fn test_transitions() {
+        let mut lifecycle = AlertLifecycle::new();
+        // nothing has fired yet.
+        assert!(lifecycle.observe(syncing(true)).is_none());
+
+        let mut first = syncing(false);
+        first.timestamp -= Duration::seconds(90);
+        let fired = lifecycle.observe(first.clone()).unwrap();
+        assert_eq!(fired.started_at, Some(first.timestamp));
+        assert!(lifecycle.observe(syncing(false)).is_none());
+        assert!(lifecycle.observe(syncing(false)).is_none());
+        assert_eq!(lifecycle.firing().count(), 1);
+
+        let resolved = lifecycle.observe(syncing(true)).unwrap();
+        assert!(resolved.is_resolved());
+        assert_eq!(resolved.started_at, Some(first.timestamp));
+        assert_eq!(resolved.fields["duration"], "1m 30s");
+        assert_eq!(lifecycle.firing().count(), 0);
+        assert!(lifecycle.observe(syncing(true)).is_none());
+
+        // fires again after it has been resolved.
+        assert!(lifecycle.observe(syncing(false)).is_some());
+    }
+
+    #[test]
+    fn test_keyed_by_validator_and_endpoint() {
+        let mut lifecycle = AlertLifecycle::new();
+        let mut a = Alert::new(
+            AlertKind::MissedBlock,
+            "http://10.0.0.1:9090",
+            "missed".to_owned(),
+        );
+        a.validator("A");
+        let mut b = a.clone();
+        b.validator("B");
+        let mut c = a.clone();
+        c.endpoint = "http://10.0.0.2:9090".to_owned();
+        assert!(lifecycle.observe(a.clone()).is_some());
+        assert!(lifecycle.observe(b).is_some());
+        assert!(lifecycle.observe(c).is_some());
+        assert!(lifecycle.observe(a).is_none());
+    }
+
+    #[test]
+    fn test_events_pass_through() {
+        let mut lifecycle = AlertLifecycle::new();
+        let alert = Alert::new(
+            AlertKind::NewProposal,
+            "http://127.0.0.1:9090",
+            "new proposal has just submitted. id: 1".to_owned(),
+        );
+        assert!(lifecycle.observe(alert.clone()).is_some());
+        assert!(lifecycle.observe(alert).is_some());
+        assert_eq!(lifecycle.firing().count(), 0);
+    }
+
+    #[test]
+    fn test_repeat_interval() {
+        let mut lifecycle = AlertLifecycle::new();
+        lifecycle.repeat_interval(AlertKind::IsSyncing, std::time::Duration::from_secs(1_800));
+        let mut first = syncing(false);
+        first.timestamp -= Duration::minutes(40);
+        assert!(lifecycle.observe(first.clone()).is_some());
+        let mut second = syncing(false);
+        second.timestamp = first.timestamp + Duration::minutes(10);
+        assert!(lifecycle.observe(second.clone()).is_none());
+        let mut third = syncing(false);
+        third.timestamp = first.timestamp + Duration::minutes(30);
+        let reminder = lifecycle.observe(third.clone()).unwrap();
+        assert_eq!(reminder.started_at, Some(first.timestamp));
+        assert_eq!(reminder.fields["duration"], "30m");
+        // the next reminder is counted from the last notification.
+        let mut fourth = syncing(false);
+        fourth.timestamp = first.timestamp + Duration::minutes(40);
+        assert!(lifecycle.observe(fourth).is_none());
+    }
+
+    #[test]
+    fn test_acknowledge() {
+        let mut lifecycle = AlertLifecycle::new();
+        lifecycle.repeat_interval(AlertKind::IsSyncing, std::time::Duration::from_secs(1_800));
+        let key = syncing(false).dedup_key();
+        assert!(!lifecycle.acknowledge(key.as_str()));
+        let mut first = syncing(false);
+        first.timestamp -= Duration::minutes(40);
+        assert!(lifecycle.observe(first.clone()).is_some());
+        assert!(lifecycle.acknowledge(key.as_str()));
+        assert!(lifecycle.is_acknowledged(key.as_str()));
+        // no reminders once acknowledged.
+        assert!(lifecycle.observe(syncing(false)).is_none());
+        assert!(lifecycle.observe(syncing(true)).is_some());
+        // the next firing is not acknowledged.
+        assert!(lifecycle.observe(syncing(false)).is_some());
+        assert!(!lifecycle.is_acknowledged(key.as_str()));
+    }
+
+    #[test]
+    fn test_renotify() {
+        let mut lifecycle = AlertLifecycle::new();
+        let key = syncing(false).dedup_key();
+        assert!(!lifecycle.renotify(key.as_str()));
+        let mut first = syncing(false);
+        first.timestamp -= Duration::minutes(40);
+        assert!(lifecycle.observe(first.clone()).is_some());
+        assert!(lifecycle.observe(syncing(false)).is_none());
+        // notified again without a repeat interval, but only once.
+        assert!(lifecycle.renotify(key.as_str()));
+        let reminder = lifecycle.observe(syncing(false)).unwrap();
+        assert!(reminder.is_reminder());
+        assert_eq!(reminder.started_at, Some(first.timestamp));
+        assert!(lifecycle.observe(syncing(false)).is_none());
+    }
+
+    #[test]
+    fn test_identical_events_throttled() {
+        let mut lifecycle = AlertLifecycle::new();
+        lifecycle.repeat_interval(
+            AlertKind::CheckFailure,
+            std::time::Duration::from_secs(1_800),
+        );
+        let failure = |message: &str, minutes: i64| {
+            let mut alert = Alert::new(
+                AlertKind::CheckFailure,
+                "http://127.0.0.1:9090",
+                message.to_owned(),
+            );
+            alert.timestamp += Duration::minutes(minutes);
+            alert
+        };
+        assert!(lifecycle.observe(failure("halted", 0)).is_some());
+        assert!(lifecycle.observe(failure("halted", 1)).is_none());
+        assert!(lifecycle.observe(failure("timeout", 1)).is_some());
+        assert!(lifecycle.observe(failure("halted", 29)).is_none());
+        assert!(lifecycle.observe(failure("halted", 30)).is_some());
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{Alert, Notifier, Result};
+use async_trait::async_trait;
+use logger::prelude::*;
+
+/// writes alerts to the logger as `error!` lines, so that the logger backend
+/// (e.g. Airbrake) keeps receiving them.
+#[derive(Debug, Default)]
+pub struct LogNotifier;
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    fn name(&self) -> &str {
+        "log"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        if alert.is_resolved() {
+            info!(
+                kind = alert.kind.as_str(),
+                severity = alert.severity.as_str(),
+                "resolved: {} (duration: {})",
+                alert.message.as_str(),
+                alert
+                    .fields
+                    .get("duration")
+                    .map(|d| d.as_str())
+                    .unwrap_or("unknown")
+            );
+            return Ok(());
+        }
+        error!(
+            kind = alert.kind.as_str(),
+            severity = alert.severity.as_str(),
+            "{}",
+            alert.message.as_str()
+        );
+        Ok(())
+    }
+}
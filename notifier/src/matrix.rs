@@ -0,0 +1,242 @@
+use crate::{Alert, Notifier, Result};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::Utc;
+use config::configs::MatrixConfig;
+use handlebars::html_escape;
+use reqwest::Url;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// posts alerts to a matrix room, and edits the same event when the alert is resolved.
+pub struct MatrixNotifier {
+    config: MatrixConfig,
+    client: reqwest::Client,
+    /// event ids of the firing alerts by dedup key.
+    events: Mutex<HashMap<String, String>>,
+    txn_counter: AtomicU64,
+}
+
+impl MatrixNotifier {
+    pub fn new(config: MatrixConfig) -> Self {
+        Self {
+            config,
+            client: crate::http_client(),
+            events: Mutex::new(HashMap::new()),
+            txn_counter: AtomicU64::new(0),
+        }
+    }
+
+    fn send_url(&self) -> Result<Url> {
+        // transaction ids must be unique per access token.
+        let txn_id = format!(
+            "teritorimon.{}.{}",
+            Utc::now().timestamp_millis(),
+            self.txn_counter.fetch_add(1, Ordering::SeqCst)
+        );
+        let mut url = Url::parse(self.config.homeserver_url.as_str())?;
+        url.path_segments_mut()
+            .map_err(|_| {
+                anyhow!(
+                    "illegal matrix homeserver_url: {}",
+                    self.config.homeserver_url
+                )
+            })?
+            .pop_if_empty()
+            .extend(&[
+                "_matrix",
+                "client",
+                "v3",
+                "rooms",
+                self.config.room_id.as_str(),
+                "send",
+                "m.room.message",
+                txn_id.as_str(),
+            ]);
+        Ok(url)
+    }
+
+    fn build_content(alert: &Alert) -> Value {
+        let (emoji, label) = if alert.is_resolved() {
+            ("✅", "RESOLVED".to_owned())
+        } else {
+            ("🚨", alert.severity.as_str().to_uppercase())
+        };
+        let mut body = vec![
+            format!("{} [{}] {}", emoji, label, alert.kind.as_str()),
+            alert.message.clone(),
+            format!("endpoint: {}", alert.endpoint),
+        ];
+        let mut details = vec![format!(
+            "<li><b>endpoint:</b> <code>{}</code></li>",
+            html_escape(alert.endpoint.as_str())
+        )];
+        if let Some(validator) = alert.validator.as_deref() {
+            body.push(format!("validator: {}", validator));
+            details.push(format!(
+                "<li><b>validator:</b> <code>{}</code></li>",
+                html_escape(validator)
+            ));
+        }
+        if let Some(height) = alert.height {
+            body.push(format!("height: {}", height));
+            details.push(format!("<li><b>height:</b> {}</li>", height));
+        }
+        for (key, value) in &alert.fields {
+            body.push(format!("{}: {}", key, value));
+            details.push(format!(
+                "<li><b>{}:</b> {}</li>",
+                html_escape(key),
+                html_escape(value)
+            ));
+        }
+        let formatted_body = format!(
+            "<p>{} <b>[{}] {}</b></p><p>{}</p><ul>{}</ul>",
+            emoji,
+            label,
+            html_escape(alert.kind.as_str()),
+            html_escape(alert.message.as_str()),
+            details.join("")
+        );
+        json!({
+            "msgtype": "m.text",
+            "body": body.join("\n"),
+            "format": "org.matrix.custom.html",
+            "formatted_body": formatted_body,
+        })
+    }
+
+    /// replaces the content of the given event. (clients without edit support show the fallback body)
+    fn build_edit(event_id: &str, content: Value) -> Value {
+        json!({
+            "msgtype": "m.text",
+            "body": format!("* {}", content["body"].as_str().unwrap_or_default()),
+            "format": "org.matrix.custom.html",
+            "formatted_body": format!("* {}", content["formatted_body"].as_str().unwrap_or_default()),
+            "m.new_content": content,
+            "m.relates_to": {
+                "rel_type": "m.replace",
+                "event_id": event_id,
+            },
+        })
+    }
+
+    async fn send(&self, content: &Value) -> Result<String> {
+        let response = self
+            .client
+            .put(self.send_url()?)
+            .bearer_auth(self.config.access_token.as_str())
+            .json(content)
+            .send()
+            .await?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(anyhow!(
+                "unexpected response from matrix status_code: {}, message: {}",
+                status,
+                body["error"].as_str().unwrap_or_default()
+            ));
+        }
+        Ok(body["event_id"].as_str().unwrap_or_default().to_owned())
+    }
+}
+
+#[async_trait]
+impl Notifier for MatrixNotifier {
+    fn name(&self) -> &str {
+        "matrix"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        let dedup_key = alert.dedup_key();
+        let content = Self::build_content(alert);
+        if alert.is_resolved() {
+            let event_id = self.events.lock().unwrap().remove(&dedup_key);
+            return match event_id {
+                Some(event_id) => {
+                    self.send(&Self::build_edit(event_id.as_str(), content))
+                        .await?;
+                    Ok(())
+                }
+                // the firing message has not been posted by this process. (e.g. restarted)
+                None => self.send(&content).await.map(|_| ()),
+            };
+        }
+        let event_id = self.send(&content).await?;
+        if !event_id.is_empty() {
+            // reminders are posted as new messages, the first one is edited on resolve.
+            self.events
+                .lock()
+                .unwrap()
+                .entry(dedup_key)
+                .or_insert(event_id);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::{self, StubResponse};
+    use crate::AlertKind;
+
+    #[tokio::test]
+    async fn test_edit_on_resolve() {
+        let (url, requests) = testing::serve(vec![
+            StubResponse::new(200, r#"{"event_id": "$firing:example.com"}"#),
+            StubResponse::new(200, r#"{"event_id": "$reminder:example.com"}"#),
+            StubResponse::new(200, r#"{"event_id": "$edit:example.com"}"#),
+        ])
+        .await;
+        let notifier = MatrixNotifier::new(MatrixConfig {
+            homeserver_url: url,
+            access_token: "syt_T0K3N".to_owned(),
+            room_id: "!ops:example.com".to_owned(),
+        });
+        let mut alert = Alert::new(
+            AlertKind::ValidatorStatus,
+            "http://127.0.0.1:9090",
+            "validator is not healthy... <jailed>".to_owned(),
+        );
+        alert.validator("torivaloper1xxx");
+        notifier.notify(&alert).await.unwrap();
+        // reminded
+        notifier.notify(&alert).await.unwrap();
+        let mut resolved = Alert::resolved(
+            AlertKind::ValidatorStatus,
+            "http://127.0.0.1:9090",
+            "validator is healthy.".to_owned(),
+        );
+        resolved.validator("torivaloper1xxx");
+        notifier.notify(&resolved).await.unwrap();
+
+        let requests = requests.await.unwrap();
+        assert_eq!(requests[0].method, "PUT");
+        assert!(requests[0]
+            .path
+            .starts_with("/_matrix/client/v3/rooms/!ops:example.com/send/m.room.message/"));
+        assert_ne!(requests[0].path, requests[1].path);
+        assert_eq!(
+            requests[0].header("authorization"),
+            Some("Bearer syt_T0K3N")
+        );
+        let firing: Value = serde_json::from_str(requests[0].body.as_str()).unwrap();
+        assert_eq!(firing["format"], "org.matrix.custom.html");
+        assert!(firing["formatted_body"]
+            .as_str()
+            .unwrap()
+            .contains("&lt;jailed&gt;"));
+        let edit: Value = serde_json::from_str(requests[2].body.as_str()).unwrap();
+        assert_eq!(edit["m.relates_to"]["rel_type"], "m.replace");
+        assert_eq!(edit["m.relates_to"]["event_id"], "$firing:example.com");
+        assert!(edit["m.new_content"]["body"]
+            .as_str()
+            .unwrap()
+            .contains("RESOLVED"));
+        assert!(notifier.events.lock().unwrap().is_empty());
+    }
+}
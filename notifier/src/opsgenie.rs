@@ -0,0 +1,192 @@
+use crate::{Alert, Notifier, Result, Severity};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use config::configs::OpsgenieConfig;
+use reqwest::Url;
+use serde_json::{json, Value};
+
+/// creates opsgenie alerts and closes them when the condition recovers.
+pub struct OpsgenieNotifier {
+    config: OpsgenieConfig,
+    client: reqwest::Client,
+}
+
+impl OpsgenieNotifier {
+    pub fn new(config: OpsgenieConfig) -> Self {
+        Self {
+            config,
+            client: crate::http_client(),
+        }
+    }
+
+    fn priority(severity: Severity) -> &'static str {
+        match severity {
+            Severity::Critical => "P1",
+            Severity::Error => "P2",
+            Severity::Warning => "P3",
+            Severity::Info => "P5",
+        }
+    }
+
+    fn url(&self, segments: &[&str]) -> Result<Url> {
+        let mut url = Url::parse(self.config.api_url.as_str())?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow!("illegal opsgenie api_url: {}", self.config.api_url))?
+            .pop_if_empty()
+            .extend(segments);
+        Ok(url)
+    }
+
+    fn build_create_request(&self, alert: &Alert) -> Value {
+        let mut tags = vec![alert.kind.as_str().to_owned()];
+        if let Some(host) = Url::parse(alert.endpoint.as_str())
+            .ok()
+            .and_then(|url| url.host_str().map(|host| host.to_owned()))
+        {
+            tags.push(host);
+        }
+        tags.extend(self.config.tags.iter().cloned());
+        let mut details = json!({
+            "endpoint": alert.endpoint.as_str(),
+        });
+        if let Some(validator) = alert.validator.as_deref() {
+            details["validator"] = json!(validator);
+        }
+        if let Some(height) = alert.height {
+            details["height"] = json!(height.to_string());
+        }
+        for (key, value) in &alert.fields {
+            details[key] = json!(value);
+        }
+        // message is limited to 130 characters, so the full text goes to the description.
+        let message: String = alert.message.chars().take(130).collect();
+        let mut request = json!({
+            "message": message,
+            "alias": alert.event_key(),
+            "description": alert.message.as_str(),
+            "tags": tags,
+            "details": details,
+            "entity": alert.validator.as_deref().unwrap_or(alert.endpoint.as_str()),
+            "source": "teritorimon",
+            "priority": Self::priority(alert.severity),
+        });
+        if let Some(team) = self.config.team.as_deref() {
+            request["responders"] = json!([{"name": team, "type": "team"}]);
+        }
+        request
+    }
+}
+
+#[async_trait]
+impl Notifier for OpsgenieNotifier {
+    fn name(&self) -> &str {
+        "opsgenie"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        let request = if alert.is_resolved() {
+            let mut url = self.url(&["v2", "alerts", alert.dedup_key().as_str(), "close"])?;
+            url.query_pairs_mut().append_pair("identifierType", "alias");
+            self.client.post(url).json(&json!({
+                "source": "teritorimon",
+                "note": alert.message.as_str(),
+            }))
+        } else {
+            self.client
+                .post(self.url(&["v2", "alerts"])?)
+                .json(&self.build_create_request(alert))
+        };
+        let response = request
+            .header("Authorization", format!("GenieKey {}", self.config.api_key))
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!(
+                "unexpected response from opsgenie status_code: {}, message: {}",
+                status,
+                response.text().await.unwrap_or_default()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::{self, StubResponse};
+    use crate::AlertKind;
+
+    const ACCEPTED: &str =
+        r#"{"result": "Request will be processed", "took": 0.1, "requestId": "x"}"#;
+
+    #[tokio::test]
+    async fn test_create_and_close() {
+        let (url, requests) = testing::serve(vec![
+            StubResponse::new(202, ACCEPTED),
+            StubResponse::new(202, ACCEPTED),
+        ])
+        .await;
+        let notifier = OpsgenieNotifier::new(OpsgenieConfig {
+            api_key: "G3N13K3Y".to_owned(),
+            api_url: url,
+            team: Some("validators".to_owned()),
+            tags: vec!["teritori".to_owned()],
+        });
+        let alert = Alert::new(
+            AlertKind::IsSyncing,
+            "http://10.0.0.1:9090",
+            "the teritori daemon: http://10.0.0.1:9090 is syncing".to_owned(),
+        );
+        notifier.notify(&alert).await.unwrap();
+        let resolved = Alert::resolved(
+            AlertKind::IsSyncing,
+            "http://10.0.0.1:9090",
+            "the teritori daemon: http://10.0.0.1:9090 is synced".to_owned(),
+        );
+        notifier.notify(&resolved).await.unwrap();
+
+        let requests = requests.await.unwrap();
+        assert_eq!(requests[0].path, "/v2/alerts");
+        assert_eq!(
+            requests[0].header("authorization"),
+            Some("GenieKey G3N13K3Y")
+        );
+        let create: Value = serde_json::from_str(requests[0].body.as_str()).unwrap();
+        assert_eq!(create["alias"], "is_syncing//http://10.0.0.1:9090");
+        assert_eq!(create["priority"], "P2");
+        assert_eq!(
+            create["tags"],
+            json!(["is_syncing", "10.0.0.1", "teritori"])
+        );
+        assert_eq!(create["responders"][0]["name"], "validators");
+        assert_eq!(
+            requests[1].path,
+            "/v2/alerts/is_syncing%2F%2Fhttp:%2F%2F10.0.0.1:9090/close?identifierType=alias"
+        );
+    }
+
+    #[test]
+    fn test_create_one_shot_events() {
+        let notifier = OpsgenieNotifier::new(OpsgenieConfig {
+            api_key: "G3N13K3Y".to_owned(),
+            api_url: "http://127.0.0.1:1".to_owned(),
+            team: None,
+            tags: vec![],
+        });
+        let mut first = Alert::new(
+            AlertKind::Slashes,
+            "http://10.0.0.1:9090",
+            "validator torivaloper1xxx has been slashed.".to_owned(),
+        );
+        first.validator("torivaloper1xxx");
+        let mut second = first.clone();
+        second.timestamp = first.timestamp + chrono::Duration::seconds(6);
+        // a second slash must not be deduplicated into the open alert of the first one.
+        assert_ne!(
+            notifier.build_create_request(&first)["alias"],
+            notifier.build_create_request(&second)["alias"]
+        );
+    }
+}
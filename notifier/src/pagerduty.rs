@@ -0,0 +1,180 @@
+use crate::{Alert, Notifier, Result, Severity};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use config::configs::PagerDutyConfig;
+use serde_json::{json, Value};
+
+/// triggers/resolves pagerduty incidents through the events api v2.
+pub struct PagerDutyNotifier {
+    config: PagerDutyConfig,
+    min_severity: Severity,
+    client: reqwest::Client,
+}
+
+impl PagerDutyNotifier {
+    pub fn new(config: PagerDutyConfig) -> Self {
+        let min_severity = config
+            .min_severity
+            .parse()
+            .expect("min_severity must be valid");
+        Self {
+            config,
+            min_severity,
+            client: crate::http_client(),
+        }
+    }
+
+    fn build_event(&self, alert: &Alert) -> Value {
+        if alert.is_resolved() {
+            return json!({
+                "routing_key": self.config.routing_key.as_str(),
+                "event_action": "resolve",
+                "dedup_key": alert.dedup_key(),
+            });
+        }
+        let mut custom_details = json!({
+            "endpoint": alert.endpoint.as_str(),
+        });
+        if let Some(validator) = alert.validator.as_deref() {
+            custom_details["validator"] = json!(validator);
+        }
+        if let Some(height) = alert.height {
+            custom_details["height"] = json!(height);
+        }
+        for (key, value) in &alert.fields {
+            custom_details[key] = json!(value);
+        }
+        // summary is limited to 1024 characters.
+        let summary: String = alert.message.chars().take(1024).collect();
+        json!({
+            "routing_key": self.config.routing_key.as_str(),
+            "event_action": "trigger",
+            "dedup_key": alert.event_key(),
+            "payload": {
+                "summary": summary,
+                "source": alert.endpoint.as_str(),
+                "severity": alert.severity.as_str(),
+                "timestamp": alert.timestamp.to_rfc3339(),
+                "component": alert.validator.as_deref().unwrap_or("teritorid"),
+                "class": alert.kind.as_str(),
+                "custom_details": custom_details,
+            },
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for PagerDutyNotifier {
+    fn name(&self) -> &str {
+        "pagerduty"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        if alert.severity < self.min_severity {
+            return Ok(());
+        }
+        let response = self
+            .client
+            .post(self.config.events_url.as_str())
+            .json(&self.build_event(alert))
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!(
+                "unexpected response from pagerduty status_code: {}, message: {}",
+                status,
+                response.text().await.unwrap_or_default()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::{self, StubResponse};
+    use crate::AlertKind;
+
+    const ACCEPTED: &str =
+        r#"{"status": "success", "message": "Event processed", "dedup_key": "x"}"#;
+
+    #[tokio::test]
+    async fn test_trigger_and_resolve() {
+        let (url, requests) = testing::serve(vec![
+            StubResponse::new(202, ACCEPTED),
+            StubResponse::new(202, ACCEPTED),
+        ])
+        .await;
+        let notifier = PagerDutyNotifier::new(PagerDutyConfig {
+            routing_key: "R0UT1NGKEY".to_owned(),
+            events_url: url,
+            min_severity: "error".to_owned(),
+        });
+        let mut alert = Alert::new(
+            AlertKind::ValidatorStatus,
+            "http://127.0.0.1:9090",
+            "validator torivaloper1xxx is not healthy...".to_owned(),
+        );
+        alert.validator("torivaloper1xxx");
+        notifier.notify(&alert).await.unwrap();
+        let mut resolved = Alert::resolved(
+            AlertKind::ValidatorStatus,
+            "http://127.0.0.1:9090",
+            "validator torivaloper1xxx is healthy.".to_owned(),
+        );
+        resolved.validator("torivaloper1xxx");
+        notifier.notify(&resolved).await.unwrap();
+
+        let requests = requests.await.unwrap();
+        let trigger: Value = serde_json::from_str(requests[0].body.as_str()).unwrap();
+        let resolve: Value = serde_json::from_str(requests[1].body.as_str()).unwrap();
+        assert_eq!(trigger["event_action"], "trigger");
+        assert_eq!(trigger["payload"]["severity"], "critical");
+        assert_eq!(resolve["event_action"], "resolve");
+        assert_eq!(
+            trigger["dedup_key"],
+            "validator_status/torivaloper1xxx/http://127.0.0.1:9090"
+        );
+        assert_eq!(trigger["dedup_key"], resolve["dedup_key"]);
+    }
+
+    #[test]
+    fn test_trigger_one_shot_events() {
+        let notifier = PagerDutyNotifier::new(PagerDutyConfig {
+            routing_key: "R0UT1NGKEY".to_owned(),
+            events_url: "http://127.0.0.1:1".to_owned(),
+            min_severity: "error".to_owned(),
+        });
+        let mut first = Alert::new(
+            AlertKind::Slashes,
+            "http://127.0.0.1:9090",
+            "validator torivaloper1xxx has been slashed.".to_owned(),
+        );
+        first.validator("torivaloper1xxx");
+        let mut second = first.clone();
+        second.timestamp = first.timestamp + chrono::Duration::seconds(6);
+        // a second slash must not be merged into the incident of the first one.
+        assert_ne!(
+            notifier.build_event(&first)["dedup_key"],
+            notifier.build_event(&second)["dedup_key"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skip_less_severe_alerts() {
+        let notifier = PagerDutyNotifier::new(PagerDutyConfig {
+            routing_key: "R0UT1NGKEY".to_owned(),
+            // nothing listens here, so a request would fail.
+            events_url: "http://127.0.0.1:1".to_owned(),
+            min_severity: "error".to_owned(),
+        });
+        let alert = Alert::new(
+            AlertKind::NewProposal,
+            "http://127.0.0.1:9090",
+            "new proposal has just submitted. id: 1".to_owned(),
+        );
+        notifier.notify(&alert).await.unwrap();
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{Alert, Notifier, Result};
+use anyhow::anyhow;
+use logger::prelude::*;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, error::TrySendError, Receiver, Sender};
+use tokio::task::JoinHandle;
+
+/// how many alerts may wait for a notifier, the others are dropped until it catches up.
+const QUEUE_SIZE: usize = 256;
+/// how long to wait for a notifier to deliver an alert, so that a stuck sink does not hold up its queue.
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// delivers the alert via the notifier, failing if it takes longer than `NOTIFY_TIMEOUT`.
+async fn notify(notifier: &dyn Notifier, alert: &Alert) -> Result<()> {
+    match tokio::time::timeout(NOTIFY_TIMEOUT, notifier.notify(alert)).await {
+        Ok(result) => result,
+        Err(_) => Err(anyhow!("timed out after {:?}", NOTIFY_TIMEOUT)),
+    }
+}
+
+/// delivers alerts via a notifier in order in a task of its own,
+/// so that a slow notifier holds up neither the dispatcher nor the other notifiers.
+pub struct NotifierQueue {
+    name: String,
+    sender: Sender<Alert>,
+    worker: Option<(Box<dyn Notifier>, Receiver<Alert>)>,
+}
+
+impl NotifierQueue {
+    pub fn new(notifier: Box<dyn Notifier>) -> Self {
+        let (sender, receiver) = mpsc::channel(QUEUE_SIZE);
+        Self {
+            name: notifier.name().to_owned(),
+            sender,
+            worker: Some((notifier, receiver)),
+        }
+    }
+
+    /// spawns the task delivering the queued alerts, which finishes once the queue is dropped.
+    /// (must be called inside a tokio runtime)
+    pub fn start(&mut self) -> Option<JoinHandle<()>> {
+        let (notifier, mut receiver) = self.worker.take()?;
+        Some(tokio::spawn(async move {
+            while let Some(alert) = receiver.recv().await {
+                if let Err(err) = notify(notifier.as_ref(), &alert).await {
+                    error!(
+                        "failed to deliver {} alert via {}: {}",
+                        alert.kind.as_str(),
+                        notifier.name(),
+                        err
+                    );
+                }
+            }
+        }))
+    }
+
+    /// queues the alert to deliver, or drops it if the notifier has fallen behind.
+    pub fn push(&self, alert: Alert) {
+        match self.sender.try_send(alert) {
+            Ok(()) => {}
+            Err(TrySendError::Full(alert)) => error!(
+                "{} has fallen behind, {} alert is dropped: {}",
+                self.name,
+                alert.kind.as_str(),
+                alert.message.as_str()
+            ),
+            Err(TrySendError::Closed(alert)) => error!(
+                "{} has stopped, {} alert is dropped: {}",
+                self.name,
+                alert.kind.as_str(),
+                alert.message.as_str()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::AlertKind;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    struct SlowNotifier {
+        delivered: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Notifier for SlowNotifier {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        async fn notify(&self, alert: &Alert) -> Result<()> {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.delivered.lock().unwrap().push(alert.message.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deliver_in_order() {
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+        let mut queue = NotifierQueue::new(Box::new(SlowNotifier {
+            delivered: delivered.clone(),
+        }));
+        let handle = queue.start().unwrap();
+        assert!(queue.start().is_none());
+        for message in ["firing", "resolved"] {
+            queue.push(Alert::new(
+                AlertKind::IsSyncing,
+                "http://127.0.0.1:9090",
+                message.to_owned(),
+            ));
+        }
+        // queuing does not wait for the deliveries.
+        assert!(delivered.lock().unwrap().is_empty());
+        drop(queue);
+        handle.await.unwrap();
+        assert_eq!(*delivered.lock().unwrap(), vec!["firing", "resolved"]);
+    }
+}
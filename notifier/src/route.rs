@@ -0,0 +1,132 @@
+use crate::Alert;
+use config::configs::RouteConfig;
+use std::collections::BTreeMap;
+
+/// a node of the routing tree which decides the receivers of alerts by their labels.
+///
+/// an alert enters the root route and goes down to the first matching child route,
+/// and also to the following siblings if the matching route has `continue` set.
+/// the deepest matching routes decide the receivers, and a route without its own
+/// receiver inherits the one of its parent.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Route {
+    pub receiver: Option<String>,
+    pub matchers: BTreeMap<String, String>,
+    pub continue_matching: bool,
+    pub routes: Vec<Route>,
+}
+
+impl Route {
+    pub fn from_config(config: &RouteConfig) -> Self {
+        Self {
+            receiver: config.receiver.clone(),
+            matchers: config.matchers.clone(),
+            continue_matching: config.continue_matching,
+            routes: config.routes.iter().map(Self::from_config).collect(),
+        }
+    }
+
+    fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.matchers
+            .iter()
+            .all(|(key, value)| labels.get(key) == Some(value))
+    }
+
+    /// the names of the receivers the alert is routed to, without duplicates.
+    pub fn receivers(&self, alert: &Alert) -> Vec<String> {
+        let labels = alert.all_labels();
+        let mut result = Vec::new();
+        self.collect(&labels, None, &mut result);
+        let mut deduped: Vec<String> = Vec::new();
+        for receiver in result {
+            if !deduped.contains(&receiver) {
+                deduped.push(receiver);
+            }
+        }
+        deduped
+    }
+
+    fn collect(
+        &self,
+        labels: &BTreeMap<String, String>,
+        parent_receiver: Option<&str>,
+        result: &mut Vec<String>,
+    ) {
+        let receiver = self.receiver.as_deref().or(parent_receiver);
+        let mut matched = false;
+        for route in &self.routes {
+            if !route.matches(labels) {
+                continue;
+            }
+            matched = true;
+            route.collect(labels, receiver, result);
+            if !route.continue_matching {
+                break;
+            }
+        }
+        if !matched {
+            if let Some(receiver) = receiver {
+                result.push(receiver.to_owned());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::AlertKind;
+
+    fn route(receiver: Option<&str>, matchers: &[(&str, &str)], continue_matching: bool) -> Route {
+        Route {
+            receiver: receiver.map(|r| r.to_owned()),
+            matchers: matchers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            continue_matching,
+            routes: Vec::new(),
+        }
+    }
+
+    fn alert(kind: AlertKind, network: &str) -> Alert {
+        let mut alert = Alert::new(kind, "http://127.0.0.1:9090", "".to_owned());
+        alert
+            .labels
+            .insert("network".to_owned(), network.to_owned());
+        alert
+    }
+
+    #[test]
+    fn test_receivers() {
+        let mut mainnet = route(None, &[("network", "mainnet")], false);
+        mainnet.routes = vec![
+            route(Some("oncall"), &[("severity", "critical")], false),
+            route(Some("oncall"), &[("kind", "missed_block")], false),
+        ];
+        let mut root = route(Some("default"), &[], false);
+        root.routes = vec![
+            route(Some("governance"), &[("kind", "new_proposal")], true),
+            route(Some("archive"), &[], true),
+            mainnet,
+        ];
+
+        assert_eq!(
+            root.receivers(&alert(AlertKind::NewProposal, "mainnet")),
+            vec!["governance", "archive", "default"]
+        );
+        assert_eq!(
+            root.receivers(&alert(AlertKind::MissedBlock, "mainnet")),
+            vec!["archive", "oncall"]
+        );
+        // inherits the receiver of the root when no child route of mainnet matches.
+        assert_eq!(
+            root.receivers(&alert(AlertKind::IsSyncing, "mainnet")),
+            vec!["archive", "default"]
+        );
+        assert_eq!(
+            root.receivers(&alert(AlertKind::MissedBlock, "testnet")),
+            vec!["archive"]
+        );
+    }
+}
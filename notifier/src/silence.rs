@@ -0,0 +1,488 @@
+use crate::{Alert, AlertKind, Result};
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use config::configs::SilenceConfig;
+use cron::Schedule;
+use logger::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// selects alerts by kind, endpoint and validator. unset conditions match any alert.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AlertMatcher {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<AlertKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator: Option<String>,
+}
+
+impl AlertMatcher {
+    pub fn matches(&self, alert: &Alert) -> bool {
+        if !self.kinds.is_empty() && !self.kinds.contains(&alert.kind) {
+            return false;
+        }
+        if let Some(endpoint) = self.endpoint.as_deref() {
+            if endpoint != alert.endpoint {
+                return false;
+            }
+        }
+        if let Some(validator) = self.validator.as_deref() {
+            if Some(validator) != alert.validator.as_deref() {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty() && self.endpoint.is_none() && self.validator.is_none()
+    }
+}
+
+/// a silence defined in config.toml which recurs on a cron schedule.
+#[derive(Clone, Debug)]
+pub struct RecurringSilence {
+    pub matcher: AlertMatcher,
+    schedule: Schedule,
+    duration: Duration,
+    timezone: Tz,
+    pub comment: Option<String>,
+}
+
+impl RecurringSilence {
+    pub fn from_config(config: &SilenceConfig) -> Result<Self> {
+        let mut kinds = Vec::new();
+        for kind in &config.kinds {
+            kinds.push(kind.parse()?);
+        }
+        Ok(Self {
+            matcher: AlertMatcher {
+                kinds,
+                endpoint: config.endpoint.clone(),
+                validator: config.validator.clone(),
+            },
+            schedule: config.get_schedule(),
+            duration: Duration::from_std(config.get_duration())?,
+            timezone: config.get_timezone(),
+            comment: config.comment.clone(),
+        })
+    }
+
+    /// whether a window started within `duration` before `now`.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        let since = now.with_timezone(&self.timezone) - self.duration;
+        match self.schedule.after(&since).next() {
+            Some(starts_at) => starts_at.with_timezone(&Utc) <= now,
+            None => false,
+        }
+    }
+}
+
+/// a one-off silence created by `teritorimon silence add`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Silence {
+    pub id: u64,
+    #[serde(flatten)]
+    pub matcher: AlertMatcher,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Silence {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.starts_at <= now && now < self.ends_at
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.ends_at <= now
+    }
+}
+
+/// the json document of the silences file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SilenceFileContents {
+    /// the id of the last silence added, not to reuse ids of the dropped ones.
+    #[serde(default)]
+    last_id: u64,
+    silences: Vec<Silence>,
+}
+
+/// ad-hoc silences persisted as json, shared between the cli and the running monitor.
+#[derive(Debug)]
+pub struct SilenceFile {
+    path: PathBuf,
+    contents: SilenceFileContents,
+    modified: Option<SystemTime>,
+}
+
+impl SilenceFile {
+    /// loads the silences from the file. (empty if it does not exist yet)
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut result = Self {
+            path: path.as_ref().to_path_buf(),
+            contents: SilenceFileContents::default(),
+            modified: None,
+        };
+        result.reload()?;
+        Ok(result)
+    }
+
+    fn modified(&self) -> Option<SystemTime> {
+        fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        self.modified = self.modified();
+        self.contents = if self.path.exists() {
+            let contents = fs::read_to_string(&self.path)
+                .with_context(|| format!("could not read silences file of {:?}", self.path))?;
+            serde_json::from_str(contents.as_str())
+                .with_context(|| format!("could not parse silences file of {:?}", self.path))?
+        } else {
+            SilenceFileContents::default()
+        };
+        Ok(())
+    }
+
+    /// picks up the changes made by the cli.
+    pub fn reload_if_changed(&mut self) -> Result<()> {
+        if self.modified() != self.modified {
+            self.reload()?;
+        }
+        Ok(())
+    }
+
+    pub fn save(&mut self) -> Result<()> {
+        let contents = serde_json::to_string_pretty(&self.contents)?;
+        // write to a temporary file first, not to let the monitor read a partial file.
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, contents)
+            .with_context(|| format!("could not write silences file of {:?}", tmp_path))?;
+        fs::rename(&tmp_path, &self.path)
+            .with_context(|| format!("could not write silences file of {:?}", self.path))?;
+        self.modified = self.modified();
+        Ok(())
+    }
+
+    pub fn silences(&self) -> &[Silence] {
+        self.contents.silences.as_slice()
+    }
+
+    /// adds a new silence, and drops the expired ones.
+    pub fn add(
+        &mut self,
+        matcher: AlertMatcher,
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+        comment: Option<String>,
+    ) -> Result<&Silence> {
+        if ends_at <= starts_at {
+            return Err(anyhow!("silence must end after it starts..."));
+        }
+        let now = Utc::now();
+        self.contents.silences.retain(|s| !s.is_expired(now));
+        self.contents.last_id += 1;
+        self.contents.silences.push(Silence {
+            id: self.contents.last_id,
+            matcher,
+            starts_at,
+            ends_at,
+            comment,
+            created_at: now,
+        });
+        Ok(self.contents.silences.last().unwrap())
+    }
+
+    /// ends the silence right now.
+    pub fn expire(&mut self, id: u64) -> Result<()> {
+        let now = Utc::now();
+        let silence = self
+            .contents
+            .silences
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| anyhow!("silence {} is not found", id))?;
+        if silence.is_expired(now) {
+            return Err(anyhow!("silence {} has already expired", id));
+        }
+        silence.ends_at = now;
+        Ok(())
+    }
+}
+
+/// decides whether an alert is silenced by a recurring window or an ad-hoc silence.
+#[derive(Debug, Default)]
+pub struct Silencer {
+    recurring: Vec<RecurringSilence>,
+    file: Option<SilenceFile>,
+    /// the alerts whose firing notification was silenced by dedup key,
+    /// so that their resolution is silenced too unless they are notified of after the silence.
+    silenced: HashMap<String, Alert>,
+}
+
+impl Silencer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_recurring(&mut self, silence: RecurringSilence) -> &mut Self {
+        self.recurring.push(silence);
+        self
+    }
+
+    pub fn file(&mut self, file: SilenceFile) -> &mut Self {
+        self.file = Some(file);
+        self
+    }
+
+    /// returns the description of the silence which silences the alert.
+    pub fn silenced_by(&mut self, alert: &Alert) -> Option<String> {
+        let key = alert.dedup_key();
+        if alert.is_resolved() {
+            return if self.silenced.remove(&key).is_some() {
+                Some("the silenced firing alert".to_owned())
+            } else {
+                None
+            };
+        }
+        match self.find_silence(alert) {
+            Some(silenced_by) => {
+                // a silenced reminder does not hide the firing alert delivered before.
+                if alert.kind.is_stateful() && !alert.is_reminder() {
+                    self.silenced.insert(key, alert.clone());
+                }
+                Some(silenced_by)
+            }
+            None => {
+                self.silenced.remove(&key);
+                None
+            }
+        }
+    }
+
+    /// forgets the silenced firing alerts which no silence matches anymore, and returns
+    /// their dedup keys to notify of them if they are still firing.
+    pub fn ended(&mut self, now: DateTime<Utc>) -> Vec<String> {
+        let silenced: Vec<Alert> = self.silenced.values().cloned().collect();
+        let mut result = Vec::new();
+        for mut alert in silenced {
+            alert.timestamp = now;
+            if self.find_silence(&alert).is_none() {
+                let key = alert.dedup_key();
+                self.silenced.remove(&key);
+                result.push(key);
+            }
+        }
+        result
+    }
+
+    fn find_silence(&mut self, alert: &Alert) -> Option<String> {
+        let now = alert.timestamp;
+        if let Some(silence) = self
+            .recurring
+            .iter()
+            .find(|s| s.is_active(now) && s.matcher.matches(alert))
+        {
+            return Some(format!(
+                "maintenance window{}",
+                silence
+                    .comment
+                    .as_deref()
+                    .map(|c| format!(" ({})", c))
+                    .unwrap_or_default()
+            ));
+        }
+        let file = self.file.as_mut()?;
+        if let Err(err) = file.reload_if_changed() {
+            warn!("failed to reload silences: {}", err);
+        }
+        file.silences()
+            .iter()
+            .find(|s| s.is_active(now) && s.matcher.matches(alert))
+            .map(|s| format!("silence #{}", s.id))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    fn missed_block(validator: &str) -> Alert {
+        let mut alert = Alert::new(
+            AlertKind::MissedBlock,
+            "http://127.0.0.1:9090",
+            "missed".to_owned(),
+        );
+        alert.validator(validator);
+        alert
+    }
+
+    #[test]
+    fn test_matcher() {
+        let matcher = AlertMatcher {
+            kinds: vec![AlertKind::IsSyncing, AlertKind::MissedBlock],
+            endpoint: Some("http://127.0.0.1:9090".to_owned()),
+            validator: None,
+        };
+        assert!(matcher.matches(&missed_block("A")));
+        let mut other_endpoint = missed_block("A");
+        other_endpoint.endpoint = "http://10.0.0.1:9090".to_owned();
+        assert!(!matcher.matches(&other_endpoint));
+        let slashes = Alert::new(
+            AlertKind::Slashes,
+            "http://127.0.0.1:9090",
+            "slashed".to_owned(),
+        );
+        assert!(!matcher.matches(&slashes));
+        let matcher = AlertMatcher {
+            validator: Some("B".to_owned()),
+            ..Default::default()
+        };
+        assert!(!matcher.matches(&missed_block("A")));
+        assert!(matcher.matches(&missed_block("B")));
+    }
+
+    #[test]
+    fn test_recurring_window() {
+        // every sunday 03:00 - 04:00 in tokyo. (18:00 - 19:00 on saturday in utc)
+        let silence = RecurringSilence {
+            matcher: AlertMatcher::default(),
+            schedule: Schedule::from_str("0 0 3 * * Sun").unwrap(),
+            duration: Duration::hours(1),
+            timezone: chrono_tz::Asia::Tokyo,
+            comment: None,
+        };
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        assert!(!silence.is_active(at("2022-10-01T17:59:59Z")));
+        assert!(silence.is_active(at("2022-10-01T18:00:00Z")));
+        assert!(silence.is_active(at("2022-10-01T18:59:59Z")));
+        assert!(!silence.is_active(at("2022-10-01T19:00:00Z")));
+        assert!(!silence.is_active(at("2022-10-02T18:30:00Z")));
+    }
+
+    #[test]
+    fn test_silence_file() {
+        let path =
+            std::env::temp_dir().join(format!("teritorimon-silences-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut file = SilenceFile::load(&path).unwrap();
+        let now = Utc::now();
+        let matcher = AlertMatcher {
+            validator: Some("A".to_owned()),
+            ..Default::default()
+        };
+        let id = file
+            .add(
+                matcher,
+                now,
+                now + Duration::hours(2),
+                Some("upgrade".to_owned()),
+            )
+            .unwrap()
+            .id;
+        file.save().unwrap();
+
+        let mut silencer = Silencer::new();
+        silencer.file(SilenceFile::load(&path).unwrap());
+        assert_eq!(
+            silencer.silenced_by(&missed_block("A")),
+            Some(format!("silence #{}", id))
+        );
+        assert_eq!(silencer.silenced_by(&missed_block("B")), None);
+
+        file.expire(id).unwrap();
+        file.save().unwrap();
+        let reloaded = SilenceFile::load(&path).unwrap();
+        silencer.file(reloaded);
+        assert_eq!(silencer.silenced_by(&missed_block("A")), None);
+
+        // ids of the dropped silences are not reused.
+        let mut file = SilenceFile::load(&path).unwrap();
+        let next_id = file
+            .add(AlertMatcher::default(), now, now + Duration::hours(1), None)
+            .unwrap()
+            .id;
+        assert_eq!(next_id, id + 1);
+        assert_eq!(file.silences().len(), 1);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_silenced_resolution() {
+        let path = std::env::temp_dir().join(format!(
+            "teritorimon-silenced-resolution-{}.json",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let mut silencer = Silencer::new();
+        silencer.file(SilenceFile::load(&path).unwrap());
+        let resolved = |validator: &str| {
+            let mut alert = Alert::resolved(
+                AlertKind::MissedBlock,
+                "http://127.0.0.1:9090",
+                "signed".to_owned(),
+            );
+            alert.validator(validator);
+            alert
+        };
+
+        // the firing alert of A is delivered before the silence.
+        assert_eq!(silencer.silenced_by(&missed_block("A")), None);
+        let now = Utc::now();
+        silencer
+            .file
+            .as_mut()
+            .unwrap()
+            .add(AlertMatcher::default(), now, now + Duration::hours(1), None)
+            .unwrap();
+        assert!(silencer.silenced_by(&missed_block("B")).is_some());
+        // the resolution closes the delivered alert.
+        assert_eq!(silencer.silenced_by(&resolved("A")), None);
+        // the resolution of the silenced one is silenced too.
+        assert!(silencer.silenced_by(&resolved("B")).is_some());
+    }
+
+    #[test]
+    fn test_silence_ended() {
+        let path = std::env::temp_dir().join(format!(
+            "teritorimon-silence-ended-{}.json",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let mut silencer = Silencer::new();
+        silencer.file(SilenceFile::load(&path).unwrap());
+        let now = Utc::now();
+        let id = silencer
+            .file
+            .as_mut()
+            .unwrap()
+            .add(AlertMatcher::default(), now, now + Duration::hours(1), None)
+            .unwrap()
+            .id;
+        let alert = missed_block("A");
+        assert!(silencer.silenced_by(&alert).is_some());
+        assert!(silencer.ended(now).is_empty());
+        // the still firing alert is notified of once the silence ends.
+        assert_eq!(
+            silencer.ended(now + Duration::hours(2)),
+            vec![alert.dedup_key()]
+        );
+        assert!(silencer.ended(now + Duration::hours(2)).is_empty());
+        // so is the one whose silence is expired from the command line.
+        assert!(silencer.silenced_by(&alert).is_some());
+        silencer.file.as_mut().unwrap().expire(id).unwrap();
+        assert_eq!(silencer.ended(Utc::now()), vec![alert.dedup_key()]);
+    }
+}
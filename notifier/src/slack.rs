@@ -0,0 +1,152 @@
+use crate::{Alert, Notifier, Result, Severity};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use config::configs::SlackConfig;
+use serde_json::{json, Value};
+
+/// posts alerts to a slack incoming webhook as block kit messages.
+pub struct SlackNotifier {
+    config: SlackConfig,
+    client: reqwest::Client,
+}
+
+impl SlackNotifier {
+    pub fn new(config: SlackConfig) -> Self {
+        Self {
+            config,
+            client: crate::http_client(),
+        }
+    }
+
+    fn build_payload(&self, alert: &Alert) -> Value {
+        let (emoji, label) = if alert.is_resolved() {
+            (":white_check_mark:", "RESOLVED".to_owned())
+        } else {
+            let emoji = match alert.severity {
+                Severity::Info => ":information_source:",
+                Severity::Warning => ":warning:",
+                Severity::Error => ":red_circle:",
+                Severity::Critical => ":rotating_light:",
+            };
+            (emoji, alert.severity.as_str().to_uppercase())
+        };
+        let title = format!("{} [{}] {}", emoji, label, alert.kind.as_str());
+        let mut fields = vec![
+            json!({"type": "mrkdwn", "text": format!("*Check*\n{}", alert.kind.as_str())}),
+            json!({"type": "mrkdwn", "text": format!("*Endpoint*\n{}", alert.endpoint.as_str())}),
+        ];
+        if let Some(validator) = alert.validator.as_deref() {
+            fields.push(json!({"type": "mrkdwn", "text": format!("*Validator*\n`{}`", validator)}));
+        }
+        if let Some(height) = alert.height {
+            fields.push(json!({"type": "mrkdwn", "text": format!("*Height*\n{}", height)}));
+        }
+        let mut blocks = vec![
+            json!({"type": "header", "text": {"type": "plain_text", "text": title}}),
+            json!({"type": "section", "text": {"type": "mrkdwn", "text": alert.message.as_str()}}),
+            json!({"type": "section", "fields": fields}),
+        ];
+        let mut context = vec![alert.timestamp.to_rfc3339()];
+        for (key, value) in &alert.fields {
+            context.push(format!("{}: {}", key, value));
+        }
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{"type": "mrkdwn", "text": context.join(" | ")}],
+        }));
+        let mut payload = json!({
+            "text": format!("{} {}", title, alert.message.as_str()),
+            "blocks": blocks,
+        });
+        if let Some(channel) = self.config.channel.as_deref() {
+            payload["channel"] = json!(channel);
+        }
+        if let Some(username) = self.config.username.as_deref() {
+            payload["username"] = json!(username);
+        }
+        if let Some(icon_emoji) = self.config.icon_emoji.as_deref() {
+            payload["icon_emoji"] = json!(icon_emoji);
+        }
+        payload
+    }
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        let response = self
+            .client
+            .post(self.config.webhook_url.as_str())
+            .json(&self.build_payload(alert))
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!(
+                "unexpected response from slack status_code: {}, message: {}",
+                status,
+                response.text().await.unwrap_or_default()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing;
+    use crate::AlertKind;
+
+    #[tokio::test]
+    async fn test_notify() {
+        let (url, request) = testing::serve_once(200, "ok").await;
+        let notifier = SlackNotifier::new(SlackConfig {
+            webhook_url: url,
+            channel: Some("#validators".to_owned()),
+            username: None,
+            icon_emoji: None,
+        });
+        let mut alert = Alert::new(
+            AlertKind::MissedBlock,
+            "http://127.0.0.1:9090",
+            "ABCD has not signed for block 100".to_owned(),
+        );
+        alert.validator("ABCD").height(100);
+        notifier.notify(&alert).await.unwrap();
+
+        let request = request.await.unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/");
+        assert_eq!(request.header("content-type"), Some("application/json"));
+        let body: Value = serde_json::from_str(request.body.as_str()).unwrap();
+        assert_eq!(body["channel"], "#validators");
+        assert_eq!(body["blocks"][0]["type"], "header");
+        let fields = body["blocks"][2]["fields"].to_string();
+        assert!(fields.contains("missed_block"));
+        assert!(fields.contains("http://127.0.0.1:9090"));
+        assert!(fields.contains("ABCD"));
+        assert!(fields.contains("100"));
+    }
+
+    #[tokio::test]
+    async fn test_notify_error_response() {
+        let (url, _request) = testing::serve_once(404, "no_team").await;
+        let notifier = SlackNotifier::new(SlackConfig {
+            webhook_url: url,
+            channel: None,
+            username: None,
+            icon_emoji: None,
+        });
+        let alert = Alert::new(
+            AlertKind::IsSyncing,
+            "http://127.0.0.1:9090",
+            "syncing".to_owned(),
+        );
+        assert!(notifier.notify(&alert).await.is_err());
+    }
+}
@@ -0,0 +1,197 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, RwLock};
+
+/// how many alerts to keep per checker.
+const MAX_LATEST_ALERTS: usize = 20;
+/// how many blocks to keep the signatures of per checker.
+const MAX_RECENT_BLOCKS: usize = 200;
+/// how many proposals to keep per checker.
+const MAX_RECENT_PROPOSALS: usize = 20;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ValidatorStatus {
+    /// the bond status. (1: unbonded, 2: unbonding, 3: bonded)
+    pub status: i32,
+    pub jailed: bool,
+}
+
+/// the blocks the validator missed to sign within the threshold window.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MissedBlockWindow {
+    pub from_height: i64,
+    pub to_height: i64,
+    /// the number of missed blocks to alert at.
+    pub threshold: u64,
+    pub missed_heights: Vec<i64>,
+}
+
+/// whether the validator has signed the block.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BlockSignature {
+    pub height: i64,
+    pub signed: bool,
+}
+
+/// a governance proposal submitted on chain.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Proposal {
+    pub proposal_id: String,
+    pub tx_hash: String,
+    pub height: Option<i64>,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// the current monitoring state of a configured checker (a teritori daemon endpoint).
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct CheckerStatus {
+    pub endpoint: String,
+    pub validator_address: Option<String>,
+    pub validator_account: Option<String>,
+    pub latest_height: Option<i64>,
+    /// when the check manager fetched the latest block last.
+    pub checked_at: Option<DateTime<Utc>>,
+    /// when each checker (e.g. is_syncing) completed a check last.
+    pub checks: BTreeMap<String, DateTime<Utc>>,
+    pub syncing: Option<bool>,
+    pub validator: Option<ValidatorStatus>,
+    pub missed_blocks: Option<MissedBlockWindow>,
+    /// the signatures of the recent blocks in the order of the heights.
+    pub recent_blocks: VecDeque<BlockSignature>,
+    /// the recent proposals, the newest first.
+    pub recent_proposals: VecDeque<Proposal>,
+    /// the latest alerts about the endpoint, the newest first.
+    pub latest_alerts: VecDeque<Value>,
+}
+
+/// the monitoring state the checkers and the dispatcher report, e.g. to serve through the http api.
+#[derive(Clone, Debug, Default)]
+pub struct CheckerStatuses {
+    inner: Arc<RwLock<BTreeMap<String, CheckerStatus>>>,
+}
+
+impl CheckerStatuses {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update<F: FnOnce(&mut CheckerStatus)>(&self, endpoint: &str, f: F) {
+        let mut statuses = self.inner.write().unwrap();
+        let status = statuses
+            .entry(endpoint.to_owned())
+            .or_insert_with(|| CheckerStatus {
+                endpoint: endpoint.to_owned(),
+                ..Default::default()
+            });
+        f(status)
+    }
+
+    /// adds the configured checker, which is reported even before the first check.
+    pub fn register(
+        &self,
+        endpoint: &str,
+        validator_address: Option<&str>,
+        validator_account: Option<&str>,
+    ) {
+        self.update(endpoint, |status| {
+            status.validator_address = validator_address.map(ToOwned::to_owned);
+            status.validator_account = validator_account.map(ToOwned::to_owned);
+        });
+    }
+
+    pub fn set_latest_height(&self, endpoint: &str, height: i64) {
+        self.update(endpoint, |status| {
+            status.latest_height = Some(height);
+            status.checked_at = Some(Utc::now());
+        });
+    }
+
+    /// the checker of the kind has completed a check.
+    pub fn checked(&self, endpoint: &str, kind: &str) {
+        self.update(endpoint, |status| {
+            status.checks.insert(kind.to_owned(), Utc::now());
+        });
+    }
+
+    pub fn set_syncing(&self, endpoint: &str, syncing: bool) {
+        self.update(endpoint, |status| status.syncing = Some(syncing));
+    }
+
+    pub fn set_validator(&self, endpoint: &str, validator: ValidatorStatus) {
+        self.update(endpoint, |status| status.validator = Some(validator));
+    }
+
+    pub fn set_missed_blocks(&self, endpoint: &str, missed_blocks: MissedBlockWindow) {
+        self.update(endpoint, |status| {
+            status.missed_blocks = Some(missed_blocks)
+        });
+    }
+
+    pub fn record_signature(&self, endpoint: &str, signature: BlockSignature) {
+        self.update(endpoint, |status| {
+            status.recent_blocks.push_back(signature);
+            while status.recent_blocks.len() > MAX_RECENT_BLOCKS {
+                status.recent_blocks.pop_front();
+            }
+        });
+    }
+
+    pub fn record_proposal(&self, endpoint: &str, proposal: Proposal) {
+        self.update(endpoint, |status| {
+            status.recent_proposals.push_front(proposal);
+            status.recent_proposals.truncate(MAX_RECENT_PROPOSALS);
+        });
+    }
+
+    pub fn record_alert(&self, endpoint: &str, alert: Value) {
+        self.update(endpoint, |status| {
+            status.latest_alerts.push_front(alert);
+            status.latest_alerts.truncate(MAX_LATEST_ALERTS);
+        });
+    }
+
+    /// the status of every checker in the order of the endpoints.
+    pub fn snapshot(&self) -> Vec<CheckerStatus> {
+        self.inner.read().unwrap().values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_snapshot() {
+        let statuses = CheckerStatuses::new();
+        let endpoint = "http://127.0.0.1:9190";
+        statuses.register(endpoint, Some("torivaloper1xxx"), None);
+        statuses.set_latest_height(endpoint, 100);
+        statuses.set_syncing(endpoint, false);
+        statuses.checked(endpoint, "is_syncing");
+        for height in 0..250 {
+            statuses.record_alert(endpoint, json!({ "height": height }));
+            statuses.record_signature(
+                endpoint,
+                BlockSignature {
+                    height,
+                    signed: height % 10 != 0,
+                },
+            );
+        }
+        let status = statuses.snapshot().remove(0);
+        assert_eq!(status.validator_address.as_deref(), Some("torivaloper1xxx"));
+        assert_eq!(status.latest_height, Some(100));
+        assert_eq!(status.syncing, Some(false));
+        assert!(status.checks.contains_key("is_syncing"));
+        assert_eq!(status.latest_alerts.len(), MAX_LATEST_ALERTS);
+        assert_eq!(status.latest_alerts[0]["height"], 249);
+        assert_eq!(status.recent_blocks.len(), MAX_RECENT_BLOCKS);
+        assert_eq!(status.recent_blocks[0].height, 50);
+        assert!(!status.recent_blocks[0].signed);
+        assert_eq!(status.recent_blocks.back().unwrap().height, 249);
+        assert!(status.validator.is_none());
+    }
+}
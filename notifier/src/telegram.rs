@@ -0,0 +1,172 @@
+use crate::{Alert, Notifier, Result, Severity};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use config::configs::TelegramConfig;
+use serde_json::{json, Value};
+
+/// sends alerts to a telegram chat through the bot api `sendMessage`.
+pub struct TelegramNotifier {
+    config: TelegramConfig,
+    client: reqwest::Client,
+}
+
+impl TelegramNotifier {
+    pub fn new(config: TelegramConfig) -> Self {
+        Self {
+            config,
+            client: crate::http_client(),
+        }
+    }
+
+    fn build_text(alert: &Alert) -> String {
+        let (emoji, label) = if alert.is_resolved() {
+            ("✅", "RESOLVED".to_owned())
+        } else {
+            let emoji = match alert.severity {
+                Severity::Info => "ℹ️",
+                Severity::Warning => "⚠️",
+                Severity::Error => "🔴",
+                Severity::Critical => "🚨",
+            };
+            (emoji, alert.severity.as_str().to_uppercase())
+        };
+        let mut lines = vec![
+            format!(
+                "{} *{}*",
+                emoji,
+                escape(format!("[{}] {}", label, alert.kind.as_str()).as_str())
+            ),
+            escape(alert.message.as_str()),
+            "".to_owned(),
+            format!("*Endpoint:* `{}`", escape_code(alert.endpoint.as_str())),
+        ];
+        if let Some(validator) = alert.validator.as_deref() {
+            lines.push(format!("*Validator:* `{}`", escape_code(validator)));
+        }
+        if let Some(height) = alert.height {
+            lines.push(format!("*Height:* {}", height));
+        }
+        for (key, value) in &alert.fields {
+            lines.push(format!("_{}:_ {}", escape(key), escape(value)));
+        }
+        lines.join("\n")
+    }
+}
+
+/// escapes a text for telegram `MarkdownV2`.
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "_*[]()~`>#+-=|{}.!\\".contains(c) {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// escapes a text inside `MarkdownV2` code entities.
+fn escape_code(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('`', "\\`")
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    fn name(&self) -> &str {
+        "telegram"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        let url = format!(
+            "{}/bot{}/sendMessage",
+            self.config.api_base_url.trim_end_matches('/'),
+            self.config.bot_token
+        );
+        let response = self
+            .client
+            .post(url.as_str())
+            .json(&json!({
+                "chat_id": self.config.chat_id.as_str(),
+                "text": Self::build_text(alert),
+                "parse_mode": "MarkdownV2",
+                "disable_web_page_preview": true,
+            }))
+            .send()
+            .await?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or_default();
+        if !status.is_success() || body["ok"] != json!(true) {
+            return Err(anyhow!(
+                "unexpected response from telegram status_code: {}, message: {}",
+                status,
+                body["description"].as_str().unwrap_or_default()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing;
+    use crate::AlertKind;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape("new proposal has just submitted. id: 12"),
+            "new proposal has just submitted\\. id: 12"
+        );
+        assert_eq!(escape("[ERROR] is_syncing"), "\\[ERROR\\] is\\_syncing");
+        assert_eq!(escape_code("a`b\\c"), "a\\`b\\\\c");
+    }
+
+    #[tokio::test]
+    async fn test_notify() {
+        let (url, request) = testing::serve_once(200, r#"{"ok": true, "result": {}}"#).await;
+        let notifier = TelegramNotifier::new(TelegramConfig {
+            bot_token: "123:ABC".to_owned(),
+            chat_id: "-1001".to_owned(),
+            api_base_url: url,
+        });
+        let mut alert = Alert::new(
+            AlertKind::NewProposal,
+            "http://127.0.0.1:9090",
+            "new proposal has just submitted. id: 12".to_owned(),
+        );
+        alert.height(100).field("proposal_id", 12);
+        notifier.notify(&alert).await.unwrap();
+
+        let request = request.await.unwrap();
+        assert_eq!(request.path, "/bot123:ABC/sendMessage");
+        let body: Value = serde_json::from_str(request.body.as_str()).unwrap();
+        assert_eq!(body["chat_id"], "-1001");
+        assert_eq!(body["parse_mode"], "MarkdownV2");
+        let text = body["text"].as_str().unwrap();
+        assert!(text.contains("new\\_proposal"));
+        assert!(text.contains("`http://127.0.0.1:9090`"));
+        assert!(text.contains("*Height:* 100"));
+    }
+
+    #[tokio::test]
+    async fn test_notify_not_ok() {
+        let (url, _request) = testing::serve_once(
+            400,
+            r#"{"ok": false, "error_code": 400, "description": "Bad Request: chat not found"}"#,
+        )
+        .await;
+        let notifier = TelegramNotifier::new(TelegramConfig {
+            bot_token: "123:ABC".to_owned(),
+            chat_id: "-1001".to_owned(),
+            api_base_url: url,
+        });
+        let alert = Alert::new(
+            AlertKind::MissedBlock,
+            "http://127.0.0.1:9090",
+            "missed".to_owned(),
+        );
+        let err = notifier.notify(&alert).await.unwrap_err();
+        assert!(err.to_string().contains("chat not found"));
+    }
+}
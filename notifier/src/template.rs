@@ -0,0 +1,193 @@
+use crate::{Alert, AlertKind, Result};
+use anyhow::Context;
+use handlebars::Handlebars;
+use logger::prelude::*;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+/// renders alert messages from user defined handlebars templates per alert kind,
+/// optionally overridden per receiver.
+///
+/// templates can refer every field of the alert (e.g. `{{validator}}`, `{{height}}`,
+/// `{{fields.proposal_id}}`, `{{labels.network}}`) and `{{vars.<name>}}` from the config,
+/// e.g. to build explorer or runbook links, and `{{ack_url}}` if the http api is enabled.
+#[derive(Debug)]
+pub struct MessageTemplates {
+    registry: Handlebars<'static>,
+    vars: BTreeMap<String, String>,
+    ack_base_url: Option<String>,
+}
+
+impl Default for MessageTemplates {
+    fn default() -> Self {
+        let mut registry = Handlebars::new();
+        // messages are plain texts, each notifier escapes them for its own format.
+        registry.register_escape_fn(handlebars::no_escape);
+        Self {
+            registry,
+            vars: BTreeMap::new(),
+            ack_base_url: None,
+        }
+    }
+}
+
+impl MessageTemplates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn template_name(receiver: Option<&str>, kind: AlertKind) -> String {
+        format!("{}/{}", receiver.unwrap_or_default(), kind.as_str())
+    }
+
+    pub fn vars(&mut self, vars: BTreeMap<String, String>) -> &mut Self {
+        self.vars = vars;
+        self
+    }
+
+    /// makes `{{ack_url}}` available in templates, which acknowledges the alert through the http api.
+    pub fn ack_base_url(&mut self, ack_base_url: &str) -> &mut Self {
+        self.ack_base_url = Some(ack_base_url.to_owned());
+        self
+    }
+
+    /// registers templates by alert kind, for the receiver or for every notifier if `None`.
+    pub fn register(
+        &mut self,
+        receiver: Option<&str>,
+        templates: &BTreeMap<String, String>,
+    ) -> Result<&mut Self> {
+        for (kind, template) in templates {
+            let kind: AlertKind = kind.parse()?;
+            self.registry
+                .register_template_string(
+                    Self::template_name(receiver, kind).as_str(),
+                    template.as_str(),
+                )
+                .with_context(|| format!("illegal template of {}: {}", kind, template))?;
+        }
+        Ok(self)
+    }
+
+    /// returns the alert with the rendered message, or as is if no template is defined.
+    pub fn render(&self, receiver: Option<&str>, alert: &Alert) -> Alert {
+        let mut name = Self::template_name(receiver, alert.kind);
+        if receiver.is_some() && !self.registry.has_template(name.as_str()) {
+            name = Self::template_name(None, alert.kind);
+        }
+        if !self.registry.has_template(name.as_str()) {
+            return alert.clone();
+        }
+        let mut context = alert.to_json();
+        context["vars"] = json!(self.vars);
+        if let Some(ack_base_url) = self.ack_base_url.as_deref() {
+            context["ack_url"] = json!(format!("{}/alerts/{}/ack", ack_base_url, alert.id()));
+        }
+        let mut result = alert.clone();
+        match self.registry.render(name.as_str(), &context) {
+            Ok(message) => result.message = message,
+            // the original message is better than nothing.
+            Err(err) => warn!("failed to render the template of {}: {}", name, err),
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn templates(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render() {
+        let mut message_templates = MessageTemplates::new();
+        message_templates
+            .vars(templates(&[("explorer_url", "https://explorer.example.com")]))
+            .register(
+                None,
+                &templates(&[(
+                    "missed_block",
+                    "{{#if (eq status \"resolved\")}}{{validator}} is signing again{{else}}{{validator}} missed {{fields.missed_blocks}}/{{fields.window}} blocks, see {{vars.explorer_url}}/blocks/{{height}}{{/if}}",
+                )]),
+            )
+            .unwrap()
+            .register(
+                Some("governance"),
+                &templates(&[("missed_block", "<{{validator}}> missed a block")]),
+            )
+            .unwrap();
+        let mut alert = Alert::new(
+            AlertKind::MissedBlock,
+            "http://127.0.0.1:9090",
+            "ABCD has not signed for block 100".to_owned(),
+        );
+        alert
+            .validator("ABCD")
+            .height(100)
+            .field("missed_blocks", 2)
+            .field("window", 10);
+
+        assert_eq!(
+            message_templates.render(None, &alert).message,
+            "ABCD missed 2/10 blocks, see https://explorer.example.com/blocks/100"
+        );
+        assert_eq!(
+            message_templates.render(Some("governance"), &alert).message,
+            "<ABCD> missed a block"
+        );
+        // falls back to the global template.
+        assert_eq!(
+            message_templates.render(Some("oncall"), &alert).message,
+            "ABCD missed 2/10 blocks, see https://explorer.example.com/blocks/100"
+        );
+        alert.status = crate::AlertStatus::Resolved;
+        assert_eq!(
+            message_templates.render(None, &alert).message,
+            "ABCD is signing again"
+        );
+        message_templates.ack_base_url("http://127.0.0.1:9300");
+        message_templates
+            .register(
+                None,
+                &templates(&[("validator_status", "{{message}} ack: {{ack_url}}")]),
+            )
+            .unwrap();
+        let mut jailed = Alert::new(
+            AlertKind::ValidatorStatus,
+            "http://127.0.0.1:9090",
+            "jailed".to_owned(),
+        );
+        jailed.validator("ABCD");
+        assert_eq!(
+            message_templates.render(None, &jailed).message,
+            format!(
+                "jailed ack: http://127.0.0.1:9300/alerts/{}/ack",
+                jailed.id()
+            )
+        );
+        // no template for the kind.
+        let syncing = Alert::new(
+            AlertKind::IsSyncing,
+            "http://127.0.0.1:9090",
+            "syncing".to_owned(),
+        );
+        assert_eq!(message_templates.render(None, &syncing).message, "syncing");
+    }
+
+    #[test]
+    fn test_illegal_template() {
+        let mut message_templates = MessageTemplates::new();
+        assert!(message_templates
+            .register(None, &templates(&[("is_syncing", "{{#if}}")]))
+            .is_err());
+        assert!(message_templates
+            .register(None, &templates(&[("unknown", "x")]))
+            .is_err());
+    }
+}
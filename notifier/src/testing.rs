@@ -0,0 +1,179 @@
+//! a tiny local http stand-in to test notifiers without reaching the real services.
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+#[derive(Debug, Clone)]
+pub struct CapturedRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl CapturedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StubResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl StubResponse {
+    pub fn new(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.to_owned(),
+        }
+    }
+}
+
+/// serves the given responses in order, one per connection, and returns every captured request.
+pub async fn serve(responses: Vec<StubResponse>) -> (String, JoinHandle<Vec<CapturedRequest>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for response in responses {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            requests.push(read_request(&mut stream).await);
+            let mut raw = format!("HTTP/1.1 {} STUB\r\n", response.status);
+            for (name, value) in &response.headers {
+                raw.push_str(format!("{}: {}\r\n", name, value).as_str());
+            }
+            raw.push_str(
+                format!(
+                    "content-length: {}\r\nconnection: close\r\n\r\n{}",
+                    response.body.len(),
+                    response.body
+                )
+                .as_str(),
+            );
+            stream.write_all(raw.as_bytes()).await.unwrap();
+            stream.shutdown().await.unwrap();
+        }
+        requests
+    });
+    (url, handle)
+}
+
+/// serves a single response and returns the captured request.
+pub async fn serve_once(status: u16, body: &str) -> (String, JoinHandle<CapturedRequest>) {
+    let (url, handle) = serve(vec![StubResponse::new(status, body)]).await;
+    let handle = tokio::spawn(async move { handle.await.unwrap().remove(0) });
+    (url, handle)
+}
+
+async fn read_request(stream: &mut tokio::net::TcpStream) -> CapturedRequest {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await.unwrap();
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if n == 0 {
+            panic!("connection closed before headers were received");
+        }
+    };
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap().split(' ');
+    let method = request_line.next().unwrap().to_owned();
+    let path = request_line.next().unwrap().to_owned();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_owned(), v.trim().to_owned()))
+        .collect();
+    let content_length = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+        .map(|(_, v)| v.parse::<usize>().unwrap())
+        .unwrap_or(0);
+    while buf.len() < header_end + content_length {
+        let n = stream.read(&mut chunk).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    CapturedRequest {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&buf[header_end..]).to_string(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CapturedMail {
+    pub mail_from: String,
+    pub rcpt_to: Vec<String>,
+    pub data: String,
+}
+
+/// accepts a single smtp session without tls/auth and returns the captured mail.
+pub async fn serve_smtp() -> (u16, JoinHandle<CapturedMail>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut mail = CapturedMail {
+            mail_from: String::new(),
+            rcpt_to: Vec::new(),
+            data: String::new(),
+        };
+        writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+        while let Some(line) = lines.next_line().await.unwrap() {
+            let command = line.to_uppercase();
+            let reply: &[u8] = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                b"250-localhost\r\n250 8BITMIME\r\n"
+            } else if command.starts_with("MAIL FROM:") {
+                mail.mail_from = strip_path(&line["MAIL FROM:".len()..]);
+                b"250 OK\r\n"
+            } else if command.starts_with("RCPT TO:") {
+                mail.rcpt_to.push(strip_path(&line["RCPT TO:".len()..]));
+                b"250 OK\r\n"
+            } else if command == "DATA" {
+                writer.write_all(b"354 go ahead\r\n").await.unwrap();
+                while let Some(line) = lines.next_line().await.unwrap() {
+                    if line == "." {
+                        break;
+                    }
+                    mail.data.push_str(line.as_str());
+                    mail.data.push('\n');
+                }
+                b"250 OK\r\n"
+            } else if command == "QUIT" {
+                writer.write_all(b"221 bye\r\n").await.unwrap();
+                break;
+            } else {
+                b"250 OK\r\n"
+            };
+            writer.write_all(reply).await.unwrap();
+        }
+        mail
+    });
+    (port, handle)
+}
+
+fn strip_path(path: &str) -> String {
+    let path = path.trim();
+    let end = path.find('>').map(|i| i + 1).unwrap_or(path.len());
+    path[..end]
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .to_owned()
+}
@@ -0,0 +1,57 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// limits the number of notifications within a sliding time window.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: usize,
+    period: Duration,
+    sent: VecDeque<Instant>,
+    /// the number of notifications dropped since the last allowed one.
+    suppressed: u64,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, period: Duration) -> Self {
+        Self {
+            limit: limit as usize,
+            period,
+            sent: VecDeque::new(),
+            suppressed: 0,
+        }
+    }
+
+    /// returns the number of notifications dropped before this one if it is allowed,
+    /// or `None` if it has to be dropped.
+    pub fn acquire(&mut self, now: Instant) -> Option<u64> {
+        while let Some(sent_at) = self.sent.front() {
+            if now.duration_since(*sent_at) < self.period {
+                break;
+            }
+            self.sent.pop_front();
+        }
+        if self.sent.len() >= self.limit {
+            self.suppressed += 1;
+            return None;
+        }
+        self.sent.push_back(now);
+        Some(std::mem::take(&mut self.suppressed))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_acquire() {
+        let mut limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+        assert_eq!(limiter.acquire(now), Some(0));
+        assert_eq!(limiter.acquire(now + Duration::from_secs(1)), Some(0));
+        assert_eq!(limiter.acquire(now + Duration::from_secs(2)), None);
+        assert_eq!(limiter.acquire(now + Duration::from_secs(59)), None);
+        assert_eq!(limiter.acquire(now + Duration::from_secs(60)), Some(2));
+        assert_eq!(limiter.acquire(now + Duration::from_secs(60)), None);
+    }
+}
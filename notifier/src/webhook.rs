@@ -0,0 +1,223 @@
+use crate::{Alert, Notifier, Result};
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use config::configs::WebhookConfig;
+use handlebars::{Context as RenderData, Handlebars, Helper, HelperResult, Output, RenderContext};
+use hmac::{Hmac, Mac};
+use reqwest::Method;
+use serde_json::Value;
+use sha2::Sha256;
+use subtle_encoding::hex;
+
+const BODY_TEMPLATE_NAME: &str = "body";
+
+/// sends alerts to any http endpoint with a user defined payload.
+pub struct WebhookNotifier {
+    config: WebhookConfig,
+    method: Method,
+    templates: Handlebars<'static>,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(config: WebhookConfig) -> Result<Self> {
+        let method = Method::from_bytes(config.method.as_bytes())
+            .with_context(|| format!("illegal webhook method: {}", config.method))?;
+        let mut templates = Handlebars::new();
+        templates.set_strict_mode(false);
+        // values are embedded into json documents in most cases.
+        templates.register_escape_fn(escape_json);
+        templates.register_helper("json", Box::new(json_helper));
+        if let Some(body_template) = config.body_template.as_deref() {
+            templates
+                .register_template_string(BODY_TEMPLATE_NAME, body_template)
+                .with_context(|| "illegal webhook body_template")?;
+        }
+        Ok(Self {
+            config,
+            method,
+            templates,
+            client: crate::http_client(),
+        })
+    }
+
+    fn build_body(&self, alert: &Alert) -> Result<String> {
+        let context = alert.to_json();
+        if self.templates.has_template(BODY_TEMPLATE_NAME) {
+            Ok(self.templates.render(BODY_TEMPLATE_NAME, &context)?)
+        } else {
+            Ok(context.to_string())
+        }
+    }
+
+    fn sign(&self, body: &str) -> Option<String> {
+        self.config.secret.as_deref().map(|secret| {
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+                .expect("HMAC can take key of any size");
+            mac.update(body.as_bytes());
+            let signature = hex::encode(mac.finalize().into_bytes());
+            format!("sha256={}", String::from_utf8_lossy(&signature))
+        })
+    }
+}
+
+/// `{{json value}}` renders the value as a json literal, e.g. `null` if missing or a quoted string.
+fn json_helper(
+    helper: &Helper<'_, '_>,
+    _: &Handlebars<'_>,
+    _: &RenderData,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let value = helper
+        .param(0)
+        .map(|param| param.value().to_string())
+        .unwrap_or_else(|| Value::Null.to_string());
+    out.write(value.as_str())?;
+    Ok(())
+}
+
+/// escapes a value to be embedded inside a json string literal.
+fn escape_json(value: &str) -> String {
+    let quoted = serde_json::to_string(value).expect("string must be serializable");
+    quoted[1..quoted.len() - 1].to_owned()
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn notify(&self, alert: &Alert) -> Result<()> {
+        let body = self.build_body(alert)?;
+        let mut request = self
+            .client
+            .request(self.method.clone(), self.config.url.as_str())
+            .header("Content-Type", "application/json");
+        for (name, value) in &self.config.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if let Some(signature) = self.sign(body.as_str()) {
+            request = request.header(self.config.signature_header.as_str(), signature);
+        }
+        let response = request.body(body).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!(
+                "unexpected response from {} status_code: {}, message: {}",
+                self.config.url,
+                status,
+                response.text().await.unwrap_or_default()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing;
+    use crate::AlertKind;
+    use std::collections::BTreeMap;
+
+    fn new_alert() -> Alert {
+        let mut alert = Alert::new(
+            AlertKind::MissedBlock,
+            "http://127.0.0.1:9090",
+            "ABCD has not signed for block \"100\"".to_owned(),
+        );
+        alert.validator("ABCD").height(100).field("window", 10);
+        alert
+    }
+
+    #[tokio::test]
+    async fn test_notify_with_template_and_signature() {
+        let (url, request) = testing::serve_once(200, "").await;
+        let mut headers = BTreeMap::new();
+        headers.insert("X-Api-Key".to_owned(), "secret-api-key".to_owned());
+        let notifier = WebhookNotifier::new(WebhookConfig {
+            url: format!("{}/alerts", url),
+            method: "PUT".to_owned(),
+            headers,
+            body_template: Some(
+                r#"{"type": "{{kind}}", "text": "{{message}}", "height": {{json height}}, "window": "{{fields.window}}"}"#
+                    .to_owned(),
+            ),
+            secret: Some("s3cr3t".to_owned()),
+            signature_header: "X-Signature".to_owned(),
+        })
+        .unwrap();
+        notifier.notify(&new_alert()).await.unwrap();
+
+        let request = request.await.unwrap();
+        assert_eq!(request.method, "PUT");
+        assert_eq!(request.path, "/alerts");
+        assert_eq!(request.header("x-api-key"), Some("secret-api-key"));
+        let body: Value = serde_json::from_str(request.body.as_str()).unwrap();
+        assert_eq!(body["type"], "missed_block");
+        assert_eq!(body["text"], "ABCD has not signed for block \"100\"");
+        assert_eq!(body["height"], 100);
+        assert_eq!(body["window"], "10");
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"s3cr3t").unwrap();
+        mac.update(request.body.as_bytes());
+        let expected = format!(
+            "sha256={}",
+            String::from_utf8(hex::encode(mac.finalize().into_bytes())).unwrap()
+        );
+        assert_eq!(request.header("x-signature"), Some(expected.as_str()));
+    }
+
+    #[test]
+    fn test_build_body_with_json_helper() {
+        let notifier = WebhookNotifier::new(WebhookConfig {
+            url: "http://127.0.0.1:8080".to_owned(),
+            method: "POST".to_owned(),
+            headers: BTreeMap::new(),
+            body_template: Some(
+                r#"{"type": "{{kind}}", "height": {{json height}}, "validator": {{json validator}}, "message": {{json message}}}"#
+                    .to_owned(),
+            ),
+            secret: None,
+            signature_header: "X-Teritorimon-Signature".to_owned(),
+        })
+        .unwrap();
+        let alert = Alert::new(
+            AlertKind::IsSyncing,
+            "http://127.0.0.1:9090",
+            "still \"syncing\"".to_owned(),
+        );
+        let body: Value =
+            serde_json::from_str(notifier.build_body(&alert).unwrap().as_str()).unwrap();
+        assert_eq!(body["type"], "is_syncing");
+        assert!(body["height"].is_null());
+        assert!(body["validator"].is_null());
+        assert_eq!(body["message"], "still \"syncing\"");
+    }
+
+    #[tokio::test]
+    async fn test_notify_without_template() {
+        let (url, request) = testing::serve_once(204, "").await;
+        let mut config = WebhookConfig {
+            url,
+            method: "POST".to_owned(),
+            headers: BTreeMap::new(),
+            body_template: None,
+            secret: None,
+            signature_header: "X-Teritorimon-Signature".to_owned(),
+        };
+        config.url.push_str("/hook");
+        let notifier = WebhookNotifier::new(config).unwrap();
+        notifier.notify(&new_alert()).await.unwrap();
+
+        let request = request.await.unwrap();
+        assert_eq!(request.method, "POST");
+        assert!(request.header("x-teritorimon-signature").is_none());
+        let body: Value = serde_json::from_str(request.body.as_str()).unwrap();
+        assert_eq!(body["kind"], "missed_block");
+        assert_eq!(body["status"], "firing");
+        assert_eq!(body["validator"], "ABCD");
+        assert_eq!(body["dedup_key"], "missed_block/ABCD/http://127.0.0.1:9090");
+    }
+}
@@ -0,0 +1,148 @@
+// renders the snapshots pushed by teritorimon on /dashboard/events.
+(function () {
+  "use strict";
+
+  var BOND_STATUSES = { 1: "unbonded", 2: "unbonding", 3: "bonded" };
+
+  function element(tag, attrs, children) {
+    var node = document.createElement(tag);
+    Object.keys(attrs || {}).forEach(function (key) {
+      node.setAttribute(key, attrs[key]);
+    });
+    (children || []).forEach(function (child) {
+      node.appendChild(typeof child === "string" ? document.createTextNode(child) : child);
+    });
+    return node;
+  }
+
+  function badge(text, kind) {
+    return element("span", { class: "badge " + kind }, [text]);
+  }
+
+  function ago(timestamp) {
+    if (!timestamp) {
+      return "-";
+    }
+    var seconds = Math.max(0, Math.round((Date.now() - Date.parse(timestamp)) / 1000));
+    if (seconds < 60) {
+      return seconds + "s ago";
+    }
+    if (seconds < 3600) {
+      return Math.floor(seconds / 60) + "m ago";
+    }
+    return Math.floor(seconds / 3600) + "h ago";
+  }
+
+  function replace(id, children) {
+    var node = document.getElementById(id);
+    while (node.firstChild) {
+      node.removeChild(node.firstChild);
+    }
+    children.forEach(function (child) {
+      node.appendChild(child);
+    });
+  }
+
+  function emptyRow(columns, text) {
+    return element("tr", {}, [element("td", { colspan: columns, class: "empty" }, [text])]);
+  }
+
+  function renderChecker(checker) {
+    var syncing = checker.syncing === null
+      ? badge("unknown", "")
+      : checker.syncing ? badge("syncing", "warning") : badge("synced", "ok");
+    var validator = "-";
+    if (checker.validator) {
+      validator = element("span", {}, [
+        badge(BOND_STATUSES[checker.validator.status] || String(checker.validator.status),
+          checker.validator.status === 3 ? "ok" : "error"),
+        " ",
+        checker.validator.jailed ? badge("jailed", "critical") : badge("not jailed", "ok"),
+      ]);
+    }
+    var missed = "-";
+    if (checker.missed_blocks) {
+      var blocks = checker.missed_blocks;
+      missed = blocks.missed_heights.length + " / " + (blocks.to_height - blocks.from_height + 1)
+        + " blocks (alerts at " + blocks.threshold + ")";
+    }
+    var rows = [
+      ["validator", checker.validator_address || checker.validator_account || "-"],
+      ["height", checker.latest_height === null ? "-" : String(checker.latest_height)],
+      ["checked", ago(checker.checked_at)],
+      ["sync", syncing],
+      ["status", validator],
+      ["missed", missed],
+    ];
+    var dl = element("dl");
+    rows.forEach(function (row) {
+      dl.appendChild(element("dt", {}, [row[0]]));
+      dl.appendChild(element("dd", {}, [row[1]]));
+    });
+    var heatmap = element("div", { class: "heatmap" }, checker.recent_blocks.map(function (block) {
+      return element("span", {
+        class: block.signed ? "signed" : "missed",
+        title: block.height + (block.signed ? " signed" : " missed"),
+      });
+    }));
+    return element("div", { class: "card" }, [element("h3", {}, [checker.endpoint]), dl, heatmap]);
+  }
+
+  function renderAlert(alert) {
+    return element("tr", {}, [
+      element("td", {}, [badge(alert.severity, alert.severity)]),
+      element("td", {}, [alert.kind]),
+      element("td", {}, [alert.endpoint]),
+      element("td", {}, [alert.message]),
+      element("td", {}, [ago(alert.started_at || alert.timestamp)]),
+      element("td", {}, [alert.acknowledged ? "yes" : "no"]),
+    ]);
+  }
+
+  function renderProposals(checkers) {
+    var proposals = {};
+    checkers.forEach(function (checker) {
+      checker.recent_proposals.forEach(function (proposal) {
+        if (!proposals[proposal.proposal_id]) {
+          proposals[proposal.proposal_id] = proposal;
+        }
+      });
+    });
+    return Object.keys(proposals)
+      .map(function (id) { return proposals[id]; })
+      .sort(function (a, b) { return Date.parse(b.detected_at) - Date.parse(a.detected_at); })
+      .map(function (proposal) {
+        return element("tr", {}, [
+          element("td", {}, [proposal.proposal_id]),
+          element("td", {}, [proposal.height === null ? "-" : String(proposal.height)]),
+          element("td", {}, [proposal.tx_hash]),
+          element("td", {}, [ago(proposal.detected_at)]),
+        ]);
+      });
+  }
+
+  function render(snapshot) {
+    replace("checkers", snapshot.checkers.map(renderChecker));
+    var alerts = snapshot.alerts.map(renderAlert);
+    replace("alerts", alerts.length ? alerts : [emptyRow(6, "no active alerts")]);
+    var proposals = renderProposals(snapshot.checkers);
+    replace("proposals", proposals.length ? proposals : [emptyRow(4, "no proposals detected yet")]);
+  }
+
+  function setConnection(text, kind) {
+    replace("connection", [document.createTextNode(text)]);
+    document.getElementById("connection").className = "badge " + kind;
+  }
+
+  // the browser reconnects by itself when the connection is lost.
+  var source = new EventSource("dashboard/events");
+  source.addEventListener("open", function () {
+    setConnection("live", "ok");
+  });
+  source.addEventListener("error", function () {
+    setConnection("reconnecting", "error");
+  });
+  source.addEventListener("snapshot", function (event) {
+    render(JSON.parse(event.data));
+  });
+})();
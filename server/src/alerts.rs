@@ -0,0 +1,175 @@
+use crate::State;
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse, Response};
+use axum::{Extension, Json};
+use notifier::active::ActiveAlert;
+use serde_json::{json, Value};
+
+pub(crate) async fn list(Extension(state): Extension<State>) -> Json<Vec<Value>> {
+    Json(
+        state
+            .active_alerts
+            .list()
+            .iter()
+            .map(ActiveAlert::to_json)
+            .collect(),
+    )
+}
+
+/// hands the acknowledgement over to the dispatcher if the alert is active.
+fn acknowledge(state: &State, id: &str) -> Option<ActiveAlert> {
+    let active = state.active_alerts.get(id)?;
+    state.alert_sender.clone().acknowledge(id);
+    Some(active)
+}
+
+pub(crate) async fn ack(Extension(state): Extension<State>, Path(id): Path<String>) -> Response {
+    match acknowledge(&state, id.as_str()) {
+        Some(_) => (
+            StatusCode::ACCEPTED,
+            Json(json!({"id": id, "acknowledged": true})),
+        )
+            .into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": format!("alert is not active: {}", id) })),
+        )
+            .into_response(),
+    }
+}
+
+pub(crate) async fn ack_page(
+    Extension(state): Extension<State>,
+    Path(id): Path<String>,
+) -> Response {
+    match state.active_alerts.get(id.as_str()) {
+        Some(active) => Html(page(
+            "Acknowledge alert",
+            format!(
+                "<p><b>[{}] {}</b></p><p>{}</p><form method=\"post\"><button type=\"submit\">Acknowledge</button></form>",
+                escape(active.alert.severity.as_str()),
+                escape(active.alert.kind.as_str()),
+                escape(active.alert.message.as_str()),
+            )
+            .as_str(),
+        ))
+        .into_response(),
+        None => not_active_page(id.as_str()),
+    }
+}
+
+pub(crate) async fn ack_form(
+    Extension(state): Extension<State>,
+    Path(id): Path<String>,
+) -> Response {
+    match acknowledge(&state, id.as_str()) {
+        Some(active) => Html(page(
+            "Alert acknowledged",
+            format!(
+                "<p>{} alert has been acknowledged. reminders and escalation are stopped until it is resolved.</p>",
+                escape(active.alert.kind.as_str())
+            )
+            .as_str(),
+        ))
+        .into_response(),
+        None => not_active_page(id.as_str()),
+    }
+}
+
+fn not_active_page(id: &str) -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Html(page(
+            "Alert not found",
+            format!(
+                "<p>alert {} is not active. it may have been resolved already.</p>",
+                escape(id)
+            )
+            .as_str(),
+        )),
+    )
+        .into_response()
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{} - teritorimon</title></head><body><h1>{}</h1>{}</body></html>",
+        title, title, body
+    )
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Server;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use futures::StreamExt;
+    use notifier::active::{ActiveAlert, ActiveAlerts};
+    use notifier::{Alert, AlertKind, AlertMessage, AlertSender};
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_list_and_ack() {
+        let (sender, mut receiver) = channel::new(16);
+        let active_alerts = ActiveAlerts::new();
+        let mut alert = Alert::new(
+            AlertKind::ValidatorStatus,
+            "http://127.0.0.1:9090",
+            "jailed".to_owned(),
+        );
+        alert.validator("torivaloper1xxx");
+        active_alerts.replace(vec![ActiveAlert {
+            alert: alert.clone(),
+            acknowledged: false,
+        }]);
+        let mut server = Server::new("127.0.0.1:0".parse().unwrap());
+        server.alerts(active_alerts, AlertSender::new(sender));
+
+        let response = server
+            .router()
+            .oneshot(Request::get("/api/v1/alerts").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let alerts: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(alerts[0]["id"], alert.id());
+        assert_eq!(alerts[0]["acknowledged"], false);
+
+        let response = server
+            .router()
+            .oneshot(
+                Request::post(format!("/api/v1/alerts/{}/ack", alert.id()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        match receiver.next().await {
+            Some(AlertMessage::Acknowledge(id)) => assert_eq!(id, alert.id()),
+            message => panic!("unexpected message: {:?}", message),
+        }
+
+        let response = server
+            .router()
+            .oneshot(
+                Request::post("/alerts/unknown/ack")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::State;
+use axum::http::header;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{Html, IntoResponse};
+use axum::Extension;
+use futures::stream::{self, Stream};
+use notifier::active::ActiveAlert;
+use notifier::status::CheckerStatuses;
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::time::Duration;
+
+/// how often to look for changes to push to the dashboard.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// the static assets are embedded in the binary.
+const INDEX_HTML: &str = include_str!("../assets/dashboard/index.html");
+const APP_JS: &str = include_str!("../assets/dashboard/app.js");
+const APP_CSS: &str = include_str!("../assets/dashboard/app.css");
+
+/// everything the dashboard shows, from the same state the checkers and the dispatcher report.
+fn snapshot(state: &State, statuses: &CheckerStatuses) -> Value {
+    json!({
+        "checkers": statuses.snapshot(),
+        "alerts": state
+            .active_alerts
+            .list()
+            .iter()
+            .map(ActiveAlert::to_json)
+            .collect::<Vec<_>>(),
+    })
+}
+
+pub(crate) async fn index() -> Html<&'static str> {
+    Html(INDEX_HTML)
+}
+
+pub(crate) async fn script() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/javascript")], APP_JS)
+}
+
+pub(crate) async fn style() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "text/css")], APP_CSS)
+}
+
+/// pushes the snapshot as a server-sent event on connect and whenever it changes.
+pub(crate) async fn events(
+    Extension(state): Extension<State>,
+    Extension(statuses): Extension<CheckerStatuses>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let interval = tokio::time::interval(REFRESH_INTERVAL);
+    let stream = stream::unfold(
+        (state, statuses, interval, None),
+        |(state, statuses, mut interval, last): (State, CheckerStatuses, _, Option<String>)| async move {
+            loop {
+                interval.tick().await;
+                let data = snapshot(&state, &statuses).to_string();
+                if last.as_ref() != Some(&data) {
+                    let event = Event::default().event("snapshot").data(data.as_str());
+                    return Some((Ok(event), (state, statuses, interval, Some(data))));
+                }
+            }
+        },
+    );
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Server;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use hyper::body::HttpBody;
+    use notifier::active::{ActiveAlert, ActiveAlerts};
+    use notifier::status::CheckerStatuses;
+    use notifier::{Alert, AlertKind, AlertSender};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_dashboard() {
+        let (sender, _receiver) = channel::new(16);
+        let active_alerts = ActiveAlerts::new();
+        let alert = Alert::new(
+            AlertKind::IsSyncing,
+            "http://127.0.0.1:9092",
+            "syncing".to_owned(),
+        );
+        active_alerts.replace(vec![ActiveAlert {
+            alert: alert.clone(),
+            acknowledged: false,
+        }]);
+        let statuses = CheckerStatuses::new();
+        statuses.register("http://127.0.0.1:9092", None, None);
+        let mut server = Server::new("127.0.0.1:0".parse().unwrap());
+        server
+            .alerts(active_alerts, AlertSender::new(sender))
+            .status(statuses)
+            .dashboard();
+
+        let response = server
+            .router()
+            .oneshot(Request::get("/dashboard").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("dashboard/app.js"));
+
+        // the snapshot is pushed as soon as connected.
+        let response = server
+            .router()
+            .oneshot(
+                Request::get("/dashboard/events")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut body = response.into_body();
+        let chunk = body.data().await.unwrap().unwrap();
+        let event = String::from_utf8_lossy(&chunk);
+        assert!(event.starts_with("event:snapshot\n"));
+        assert!(event.contains(alert.id().as_str()));
+        assert!(event.contains("http://127.0.0.1:9092"));
+    }
+}
@@ -0,0 +1,160 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug)]
+struct EndpointHealth {
+    /// when the check manager of the endpoint completed a tick last.
+    last_tick: Option<Instant>,
+    /// why the endpoint was unreachable on the last tick.
+    error: Option<String>,
+}
+
+#[derive(Debug)]
+struct HealthState {
+    last_tick: Instant,
+    endpoints: BTreeMap<String, EndpointHealth>,
+}
+
+/// what the main tick loop reports to tell whether teritorimon is alive and ready.
+#[derive(Clone, Debug)]
+pub struct Health {
+    /// how long a tick may take before teritorimon is considered stuck.
+    stale_after: Duration,
+    inner: Arc<RwLock<HealthState>>,
+}
+
+impl Health {
+    pub fn new(stale_after: Duration) -> Self {
+        Self {
+            stale_after,
+            inner: Arc::new(RwLock::new(HealthState {
+                last_tick: Instant::now(),
+                endpoints: BTreeMap::new(),
+            })),
+        }
+    }
+
+    /// the endpoint is not ready until its first tick.
+    pub fn add_endpoint(&self, endpoint: &str) {
+        self.inner.write().unwrap().endpoints.insert(
+            endpoint.to_owned(),
+            EndpointHealth {
+                last_tick: None,
+                error: None,
+            },
+        );
+    }
+
+    /// the main tick loop has advanced.
+    pub fn tick(&self, now: Instant) {
+        self.inner.write().unwrap().last_tick = now;
+    }
+
+    /// the check manager of the endpoint has completed a tick.
+    pub fn report(&self, endpoint: &str, now: Instant, error: Option<String>) {
+        self.inner.write().unwrap().endpoints.insert(
+            endpoint.to_owned(),
+            EndpointHealth {
+                last_tick: Some(now),
+                error,
+            },
+        );
+    }
+
+    pub fn is_alive(&self, now: Instant) -> bool {
+        now.duration_since(self.inner.read().unwrap().last_tick) < self.stale_after
+    }
+
+    fn liveness(&self, now: Instant) -> (bool, Value) {
+        let alive = self.is_alive(now);
+        let last_tick = self.inner.read().unwrap().last_tick;
+        (
+            alive,
+            json!({
+                "alive": alive,
+                "last_tick_seconds_ago": now.duration_since(last_tick).as_secs(),
+            }),
+        )
+    }
+
+    fn readiness(&self, now: Instant) -> (bool, Value) {
+        let state = self.inner.read().unwrap();
+        let mut ready = true;
+        let mut endpoints = Vec::new();
+        for (endpoint, health) in &state.endpoints {
+            let ticking = health
+                .last_tick
+                .map(|last_tick| now.duration_since(last_tick) < self.stale_after)
+                .unwrap_or_default();
+            let reachable = health.last_tick.is_some() && health.error.is_none();
+            ready &= ticking && reachable;
+            endpoints.push(json!({
+                "endpoint": endpoint,
+                "ready": ticking && reachable,
+                "reachable": reachable,
+                "last_tick_seconds_ago": health
+                    .last_tick
+                    .map(|last_tick| now.duration_since(last_tick).as_secs()),
+                "error": health.error,
+            }));
+        }
+        (ready, json!({ "ready": ready, "endpoints": endpoints }))
+    }
+}
+
+fn respond(ok: bool, body: Value) -> Response {
+    let status = if ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(body)).into_response()
+}
+
+/// fails if the main tick loop has stopped advancing.
+pub(crate) async fn healthz(Extension(health): Extension<Health>) -> Response {
+    let (alive, body) = health.liveness(Instant::now());
+    respond(alive, body)
+}
+
+/// fails unless every check manager has completed a tick recently and reached its endpoint.
+pub(crate) async fn readyz(Extension(health): Extension<Health>) -> Response {
+    let (ready, body) = health.readiness(Instant::now());
+    respond(ready, body)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ENDPOINT: &str = "http://127.0.0.1:9090";
+
+    #[test]
+    fn test_health() {
+        let health = Health::new(Duration::from_secs(30));
+        health.add_endpoint(ENDPOINT);
+        let now = Instant::now();
+        assert!(health.is_alive(now));
+        assert!(!health.readiness(now).0);
+
+        health.report(ENDPOINT, now, None);
+        health.tick(now);
+        assert!(health.readiness(now).0);
+        health.report(ENDPOINT, now, Some("connection refused".to_owned()));
+        let (ready, body) = health.readiness(now);
+        assert!(!ready);
+        assert_eq!(body["endpoints"][0]["reachable"], false);
+        assert_eq!(body["endpoints"][0]["error"], "connection refused");
+
+        // the tick loop is stuck.
+        let later = now + Duration::from_secs(30);
+        health.report(ENDPOINT, now, None);
+        assert!(!health.is_alive(later));
+        assert!(!health.readiness(later).0);
+    }
+}
@@ -0,0 +1,207 @@
+mod alerts;
+mod dashboard;
+mod health;
+mod status;
+
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Extension, Router};
+use logger::prelude::*;
+use notifier::active::ActiveAlerts;
+use notifier::status::CheckerStatuses;
+use notifier::AlertSender;
+use std::net::SocketAddr;
+
+pub use health::Health;
+
+pub type Result<T> = anyhow::Result<T>;
+
+#[derive(Clone)]
+struct State {
+    active_alerts: ActiveAlerts,
+    alert_sender: AlertSender,
+}
+
+/// the local http api to list and acknowledge active alerts and report the monitoring state,
+/// the prometheus exporter and health checks.
+pub struct Server {
+    listen: SocketAddr,
+    alerts: Option<State>,
+    dashboard: bool,
+    statuses: Option<CheckerStatuses>,
+    metrics: bool,
+    health: Option<Health>,
+}
+
+impl Server {
+    pub fn new(listen: SocketAddr) -> Self {
+        Self {
+            listen,
+            alerts: None,
+            dashboard: false,
+            statuses: None,
+            metrics: false,
+            health: None,
+        }
+    }
+
+    pub fn listen(&self) -> SocketAddr {
+        self.listen
+    }
+
+    /// serves the api to list and acknowledge active alerts.
+    pub fn alerts(&mut self, active_alerts: ActiveAlerts, alert_sender: AlertSender) -> &mut Self {
+        self.alerts = Some(State {
+            active_alerts,
+            alert_sender,
+        });
+        self
+    }
+
+    /// serves the web dashboard on `/dashboard`, which requires the alerts and the status api.
+    pub fn dashboard(&mut self) -> &mut Self {
+        self.dashboard = true;
+        self
+    }
+
+    /// serves the current monitoring state of every checker on `/api/v1/status`.
+    pub fn status(&mut self, statuses: CheckerStatuses) -> &mut Self {
+        self.statuses = Some(statuses);
+        self
+    }
+
+    /// serves prometheus metrics on `/metrics`.
+    pub fn metrics(&mut self) -> &mut Self {
+        self.metrics = true;
+        self
+    }
+
+    /// serves `/healthz` and `/readyz` for the orchestrator to probe.
+    pub fn health(&mut self, health: Health) -> &mut Self {
+        self.health = Some(health);
+        self
+    }
+
+    fn router(&self) -> Router {
+        let mut router = Router::new();
+        if let Some(state) = self.alerts.as_ref() {
+            router = router
+                .route("/api/v1/alerts", get(alerts::list))
+                .route("/api/v1/alerts/:id/ack", post(alerts::ack))
+                // the links embedded in messages, which ask to confirm before acknowledging.
+                .route(
+                    "/alerts/:id/ack",
+                    get(alerts::ack_page).post(alerts::ack_form),
+                );
+            if self.dashboard && self.statuses.is_some() {
+                router = router
+                    .route("/dashboard", get(dashboard::index))
+                    .route("/dashboard/app.js", get(dashboard::script))
+                    .route("/dashboard/app.css", get(dashboard::style))
+                    .route("/dashboard/events", get(dashboard::events));
+            }
+            router = router.layer(Extension(state.clone()));
+        }
+        if let Some(statuses) = self.statuses.as_ref() {
+            // also layered over the dashboard routes above.
+            router = router
+                .route("/api/v1/status", get(status::list))
+                .layer(Extension(statuses.clone()));
+        }
+        if self.metrics {
+            router = router.route("/metrics", get(export_metrics));
+        }
+        if let Some(health) = self.health.as_ref() {
+            router = router
+                .route("/healthz", get(health::healthz))
+                .route("/readyz", get(health::readyz))
+                .layer(Extension(health.clone()));
+        }
+        router
+    }
+
+    pub async fn run(self) {
+        let builder = match axum::Server::try_bind(&self.listen) {
+            Ok(builder) => builder,
+            Err(err) => {
+                error!("failed to start http server on {}: {}", self.listen, err);
+                return;
+            }
+        };
+        info!("http server is listening on {}", self.listen);
+        if let Err(err) = builder.serve(self.router().into_make_service()).await {
+            error!("http server has stopped: {}", err);
+        }
+    }
+}
+
+async fn export_metrics() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics::gather(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::Server;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use notifier::status::CheckerStatuses;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_metrics() {
+        metrics::LATEST_BLOCK_HEIGHT
+            .with_label_values(&["http://127.0.0.1:9090"])
+            .set(100);
+        let mut server = Server::new("127.0.0.1:0".parse().unwrap());
+        server.metrics();
+
+        let response = server
+            .router()
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert!(String::from_utf8_lossy(&body)
+            .contains("teritorimon_latest_block_height{endpoint=\"http://127.0.0.1:9090\"} 100"));
+
+        // the alerts api is not served unless enabled.
+        let response = server
+            .router()
+            .oneshot(Request::get("/api/v1/alerts").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_status() {
+        let statuses = CheckerStatuses::new();
+        statuses.register("http://127.0.0.1:9090", Some("torivaloper1xxx"), None);
+        statuses.set_latest_height("http://127.0.0.1:9090", 100);
+        statuses.register("http://127.0.0.1:9091", None, None);
+        let mut server = Server::new("127.0.0.1:0".parse().unwrap());
+        server.status(statuses);
+
+        let response = server
+            .router()
+            .oneshot(
+                Request::get("/api/v1/status?endpoint=http://127.0.0.1:9090")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let statuses: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(statuses.as_array().unwrap().len(), 1);
+        assert_eq!(statuses[0]["validator_address"], "torivaloper1xxx");
+        assert_eq!(statuses[0]["latest_height"], 100);
+        assert_eq!(statuses[0]["syncing"], serde_json::Value::Null);
+    }
+}
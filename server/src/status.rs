@@ -0,0 +1,29 @@
+use axum::extract::Query;
+use axum::{Extension, Json};
+use notifier::status::{CheckerStatus, CheckerStatuses};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct StatusQuery {
+    endpoint: Option<String>,
+}
+
+/// the current monitoring state of every checker, or of the one with the endpoint.
+pub(crate) async fn list(
+    Extension(statuses): Extension<CheckerStatuses>,
+    Query(query): Query<StatusQuery>,
+) -> Json<Vec<CheckerStatus>> {
+    Json(
+        statuses
+            .snapshot()
+            .into_iter()
+            .filter(|status| {
+                query
+                    .endpoint
+                    .as_ref()
+                    .map(|endpoint| &status.endpoint == endpoint)
+                    .unwrap_or(true)
+            })
+            .collect(),
+    )
+}
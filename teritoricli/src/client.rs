@@ -2,6 +2,9 @@ use crate::Result;
 use anyhow::anyhow;
 use anyhow::Context;
 use logger::prelude::*;
+use metrics::trace::KeyValue;
+use std::future::Future;
+use std::time::Instant;
 use teritori_grpc_client as proto;
 use teritori_grpc_client::tonic::{self, transport::Channel, Code};
 
@@ -55,6 +58,11 @@ impl TeritoridClient {
     }
 
     pub async fn fetch_syncing(&mut self) -> anyhow::Result<bool> {
+        let endpoint = self.endpoint.clone();
+        observe(endpoint.as_str(), "fetch_syncing", self.get_syncing()).await
+    }
+
+    async fn get_syncing(&mut self) -> anyhow::Result<bool> {
         let mut client =
             proto::cosmos::base::tendermint::v1beta1::service_client::ServiceClient::new(
                 self.as_connection().await?,
@@ -74,6 +82,18 @@ impl TeritoridClient {
 
     pub async fn fetch_latest_block(
         &mut self,
+    ) -> anyhow::Result<proto::cosmos::base::tendermint::v1beta1::GetLatestBlockResponse> {
+        let endpoint = self.endpoint.clone();
+        observe(
+            endpoint.as_str(),
+            "fetch_latest_block",
+            self.get_latest_block(),
+        )
+        .await
+    }
+
+    async fn get_latest_block(
+        &mut self,
     ) -> anyhow::Result<proto::cosmos::base::tendermint::v1beta1::GetLatestBlockResponse> {
         let mut client =
             proto::cosmos::base::tendermint::v1beta1::service_client::ServiceClient::new(
@@ -95,6 +115,19 @@ impl TeritoridClient {
     pub async fn fetch_block_by_height(
         &mut self,
         height: i64,
+    ) -> anyhow::Result<proto::cosmos::base::tendermint::v1beta1::GetBlockByHeightResponse> {
+        let endpoint = self.endpoint.clone();
+        observe(
+            endpoint.as_str(),
+            "fetch_block_by_height",
+            self.get_block_by_height(height),
+        )
+        .await
+    }
+
+    async fn get_block_by_height(
+        &mut self,
+        height: i64,
     ) -> anyhow::Result<proto::cosmos::base::tendermint::v1beta1::GetBlockByHeightResponse> {
         let mut client =
             proto::cosmos::base::tendermint::v1beta1::service_client::ServiceClient::new(
@@ -120,6 +153,14 @@ impl TeritoridClient {
     pub async fn fetch_tx_by_hash(
         &mut self,
         tx_hash: &str,
+    ) -> anyhow::Result<Option<proto::cosmos::base::abci::v1beta1::TxResponse>> {
+        let endpoint = self.endpoint.clone();
+        observe(endpoint.as_str(), "fetch_tx_by_hash", self.get_tx(tx_hash)).await
+    }
+
+    async fn get_tx(
+        &mut self,
+        tx_hash: &str,
     ) -> anyhow::Result<Option<proto::cosmos::base::abci::v1beta1::TxResponse>> {
         let mut client = proto::cosmos::tx::v1beta1::service_client::ServiceClient::new(
             self.as_connection().await?,
@@ -153,6 +194,19 @@ impl TeritoridClient {
     pub async fn fetch_validator_status(
         &mut self,
         validator_address: String,
+    ) -> anyhow::Result<Option<proto::cosmos::staking::v1beta1::Validator>> {
+        let endpoint = self.endpoint.clone();
+        observe(
+            endpoint.as_str(),
+            "fetch_validator_status",
+            self.get_validator(validator_address),
+        )
+        .await
+    }
+
+    async fn get_validator(
+        &mut self,
+        validator_address: String,
     ) -> anyhow::Result<Option<proto::cosmos::staking::v1beta1::Validator>> {
         let mut client = proto::cosmos::staking::v1beta1::query_client::QueryClient::new(
             self.as_connection().await?,
@@ -188,6 +242,21 @@ impl TeritoridClient {
         validator_address: String,
         starting_height: u64,
         ending_height: u64,
+    ) -> anyhow::Result<Vec<proto::cosmos::distribution::v1beta1::ValidatorSlashEvent>> {
+        let endpoint = self.endpoint.clone();
+        observe(
+            endpoint.as_str(),
+            "fetch_slashes",
+            self.get_validator_slashes(validator_address, starting_height, ending_height),
+        )
+        .await
+    }
+
+    async fn get_validator_slashes(
+        &mut self,
+        validator_address: String,
+        starting_height: u64,
+        ending_height: u64,
     ) -> anyhow::Result<Vec<proto::cosmos::distribution::v1beta1::ValidatorSlashEvent>> {
         let mut client = proto::cosmos::distribution::v1beta1::query_client::QueryClient::new(
             self.as_connection().await?,
@@ -223,3 +292,20 @@ impl TeritoridClient {
         }
     }
 }
+
+/// runs a grpc request of the method in a span, and observes its duration and failure.
+async fn observe<T>(
+    endpoint: &str,
+    method: &'static str,
+    request: impl Future<Output = anyhow::Result<T>>,
+) -> anyhow::Result<T> {
+    let started_at = Instant::now();
+    let result = metrics::trace::in_result_span(
+        format!("TeritoridClient::{}", method),
+        vec![KeyValue::new("endpoint", endpoint.to_owned())],
+        request,
+    )
+    .await;
+    metrics::observe_grpc_request(endpoint, method, started_at, &result);
+    result
+}
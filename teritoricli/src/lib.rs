@@ -1,7 +1,8 @@
 mod client;
 
 pub use client::TeritoridClient;
-use futures::lock::Mutex;
+use futures::lock::{Mutex, MutexGuard};
+use metrics::trace::KeyValue;
 use once_cell::sync::OnceCell;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -18,6 +19,16 @@ pub fn get_client(endpoint: &str) -> &'static Mutex<TeritoridClient> {
         .expect("teritori GRPC client with specific endpoint must be initialized...")
 }
 
+/// waits for the client of the endpoint, which is shared with the other checkers, in a span.
+pub async fn lock_client(endpoint: &str) -> MutexGuard<'static, TeritoridClient> {
+    metrics::trace::in_span(
+        "TeritoridClient::lock",
+        vec![KeyValue::new("endpoint", endpoint.to_owned())],
+        get_client(endpoint).lock(),
+    )
+    .await
+}
+
 pub fn set_client_pool(client_pool: HashMap<String, Arc<Mutex<TeritoridClient>>>) {
     if GLOBAL_CLIENT_POOL.set(client_pool).is_err() {
         eprintln!("Global teritori GRPC client pool has already been set");